@@ -1,11 +1,105 @@
+const STYLE_SOLID: i32 = 0;
+const STYLE_LINEAR_GRADIENT: i32 = 1;
+const STYLE_RADIAL_GRADIENT: i32 = 2;
+const STYLE_PANEL: i32 = 3;
+const MAX_STOPS: i32 = 8;
+
+struct Background {
+    color: vec4<f32>,
+    // x: style, y: number of gradient stops, z: angle of linear gradient (radian)
+    params: vec4<f32>,
+    // xy: window size, zw: panel size
+    size: vec4<f32>,
+    // x: corner radius, y: border width
+    panel: vec4<f32>,
+    border_color: vec4<f32>,
+    stop_positions: array<vec4<f32>, 2>,
+    stop_colors: array<vec4<f32>, 8>,
+    // x: elapsed seconds since launch, y: timer progress (0.0 - 1.0),
+    // z: timer value in seconds, w: mode (0: clock, 1: count up, 2: count down)
+    time: vec4<f32>,
+}
 struct VertexOutput {
     @builtin(position) clip_position: vec4<f32>,
-    @location(0) color: vec4<f32>,
+}
+
+@group(0) @binding(0) var<uniform> background: Background;
+
+fn premultiply(color: vec4<f32>) -> vec4<f32> {
+    return vec4<f32>(color.xyz * color.w, color.w);
+}
+
+fn stop_position(i: i32) -> f32 {
+    return background.stop_positions[i / 4][i % 4];
+}
+
+fn gradient(t: f32) -> vec4<f32> {
+    let count = i32(background.params.y);
+    var color = background.stop_colors[0];
+
+    for (var i = 1; i < min(count, MAX_STOPS); i++) {
+        let start = stop_position(i - 1);
+        let end = stop_position(i);
+
+        if (t >= end) {
+            color = background.stop_colors[i];
+        } else if (t > start) {
+            color = mix(
+                background.stop_colors[i - 1],
+                background.stop_colors[i],
+                (t - start) / max(end - start, 0.0001),
+            );
+        }
+    }
+
+    return color;
+}
+
+// Signed distance from rounded rectangle centered at origin
+fn rounded_rectangle(p: vec2<f32>, half_size: vec2<f32>, radius: f32) -> f32 {
+    let r = min(radius, min(half_size.x, half_size.y));
+    let q = abs(p) - half_size + vec2<f32>(r);
+
+    return length(max(q, vec2<f32>(0.0))) + min(max(q.x, q.y), 0.0) - r;
 }
 
 @fragment
 fn main(
     in: VertexOutput,
 ) -> @location(0) vec4<f32> {
-    return in.color;
+    let style = i32(background.params.x);
+    let window_size = background.size.xy;
+    let uv = in.clip_position.xy / window_size;
+
+    switch style {
+        case STYLE_LINEAR_GRADIENT: {
+            let direction = vec2<f32>(cos(background.params.z), sin(background.params.z));
+            let extent = abs(direction.x) + abs(direction.y);
+            let t = dot(uv - vec2<f32>(0.5), direction) / extent + 0.5;
+
+            return premultiply(gradient(clamp(t, 0.0, 1.0)));
+        }
+        case STYLE_RADIAL_GRADIENT: {
+            let p = in.clip_position.xy - window_size / 2.0;
+            let t = length(p) / length(window_size / 2.0);
+
+            return premultiply(gradient(clamp(t, 0.0, 1.0)));
+        }
+        case STYLE_PANEL: {
+            let p = in.clip_position.xy - window_size / 2.0;
+            let distance = rounded_rectangle(p, background.size.zw / 2.0, background.panel.x);
+            let border = background.panel.y;
+            let fill = select(
+                background.color,
+                background.border_color,
+                border > 0.0 && distance > -border,
+            );
+            let coverage = clamp(0.5 - distance, 0.0, 1.0);
+
+            return premultiply(vec4<f32>(fill.xyz, fill.w * coverage));
+        }
+        default: {
+            return premultiply(background.color);
+        }
+    }
 }
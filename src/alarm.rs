@@ -0,0 +1,189 @@
+// Copyright 2025 Kensuke Saito
+// SPDX-License-Identifier: MIT
+
+use crate::{AlarmConfig, Weekday};
+use anyhow::{bail, Result};
+use chrono::{Datelike, NaiveDateTime, NaiveTime};
+
+/// Parse time of day (`HH:MM` or `HH:MM:SS`, 24-hour clock)
+pub fn parse_time_of_day(input: &str) -> Result<NaiveTime> {
+    let input = input.trim();
+
+    NaiveTime::parse_from_str(input, "%H:%M:%S")
+        .or_else(|_| NaiveTime::parse_from_str(input, "%H:%M"))
+        .or_else(|_| bail!("Invalid time \"{input}\" (expected HH:MM or HH:MM:SS)"))
+}
+
+/// Format time of day into the form accepted by `parse_time_of_day`
+pub fn format_time_of_day(time: &NaiveTime) -> String {
+    match time.format("%S").to_string().as_str() {
+        "00" => time.format("%H:%M").to_string(),
+        _ => time.format("%H:%M:%S").to_string(),
+    }
+}
+
+impl AlarmConfig {
+    /// Whether the alarm is due between `from` (exclusive) and `to` (inclusive)
+    pub fn is_due(&self, from: NaiveDateTime, to: NaiveDateTime) -> bool {
+        from.date()
+            .iter_days()
+            .take_while(|date| *date <= to.date())
+            .filter(|date| self.fires_on(date.weekday()))
+            .map(|date| date.and_time(self.time))
+            .any(|time| from < time && time <= to)
+    }
+
+    fn fires_on(&self, weekday: chrono::Weekday) -> bool {
+        self.weekdays.is_empty()
+            || self
+                .weekdays
+                .iter()
+                .any(|day| weekday == chrono::Weekday::from(*day))
+    }
+}
+
+impl From<Weekday> for chrono::Weekday {
+    fn from(value: Weekday) -> Self {
+        match value {
+            Weekday::Mon => Self::Mon,
+            Weekday::Tue => Self::Tue,
+            Weekday::Wed => Self::Wed,
+            Weekday::Thu => Self::Thu,
+            Weekday::Fri => Self::Fri,
+            Weekday::Sat => Self::Sat,
+            Weekday::Sun => Self::Sun,
+        }
+    }
+}
+
+/// Serde helper for times of day
+pub mod serde_time_of_day {
+    use super::{format_time_of_day, parse_time_of_day};
+    use chrono::NaiveTime;
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(time: &NaiveTime, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format_time_of_day(time))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<NaiveTime, D::Error> {
+        parse_time_of_day(&String::deserialize(deserializer)?).map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn alarm(time: &str, weekdays: &[Weekday]) -> AlarmConfig {
+        toml::from_str::<AlarmConfig>(&format!("time = \"{time}\""))
+            .map(|alarm| AlarmConfig {
+                weekdays: weekdays.to_vec(),
+                ..alarm
+            })
+            .unwrap()
+    }
+
+    fn at(datetime: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(datetime, "%Y-%m-%d %H:%M:%S").unwrap()
+    }
+
+    #[test]
+    fn due_alarms() {
+        // 2025-01-06 is a Monday
+        let cases = [
+            (
+                "12:30",
+                &[][..],
+                "2025-01-06 12:29:59",
+                "2025-01-06 12:30:00",
+            ),
+            (
+                "12:30",
+                &[][..],
+                "2025-01-06 12:00:00",
+                "2025-01-06 13:00:00",
+            ),
+            (
+                "00:00",
+                &[][..],
+                "2025-01-06 23:59:59",
+                "2025-01-07 00:00:00",
+            ),
+            (
+                "12:30",
+                &[Weekday::Mon][..],
+                "2025-01-06 12:29:59",
+                "2025-01-06 12:30:00",
+            ),
+            (
+                "12:30",
+                &[Weekday::Tue][..],
+                "2025-01-06 13:00:00",
+                "2025-01-07 13:00:00",
+            ),
+            (
+                "12:30:15",
+                &[][..],
+                "2025-01-06 12:30:14",
+                "2025-01-06 12:30:15",
+            ),
+        ];
+
+        for (time, weekdays, from, to) in cases {
+            assert!(
+                alarm(time, weekdays).is_due(at(from), at(to)),
+                "{time} {from} {to}"
+            );
+        }
+    }
+
+    #[test]
+    fn not_due_alarms() {
+        let cases = [
+            (
+                "12:30",
+                &[][..],
+                "2025-01-06 12:30:00",
+                "2025-01-06 12:31:00",
+            ),
+            (
+                "12:30",
+                &[][..],
+                "2025-01-06 12:28:00",
+                "2025-01-06 12:29:59",
+            ),
+            (
+                "12:30",
+                &[][..],
+                "2025-01-06 12:30:00",
+                "2025-01-06 12:30:00",
+            ),
+            (
+                "12:30",
+                &[Weekday::Tue][..],
+                "2025-01-06 12:29:59",
+                "2025-01-06 12:30:00",
+            ),
+            (
+                "00:00",
+                &[Weekday::Mon][..],
+                "2025-01-06 23:59:59",
+                "2025-01-07 00:00:00",
+            ),
+        ];
+
+        for (time, weekdays, from, to) in cases {
+            assert!(
+                !alarm(time, weekdays).is_due(at(from), at(to)),
+                "{time} {from} {to}"
+            );
+        }
+    }
+
+    #[test]
+    fn require_alarm_time() {
+        assert!(toml::from_str::<AlarmConfig>("label = \"Lunch\"").is_err());
+        assert!(toml::from_str::<AlarmConfig>("time = \"25:00\"").is_err());
+    }
+}
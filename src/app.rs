@@ -1,17 +1,37 @@
 // Copyright 2025 Kensuke Saito
 // SPDX-License-Identifier: MIT
 
-use super::Needle;
+use super::{
+    ipc::{self, Request},
+    AppConfig, Needle,
+};
 use anyhow::Result;
 use needle_core::NeedleConfig;
 use std::{cell::RefCell, rc::Rc};
 use winit::event_loop::{ControlFlow, EventLoop};
 
-pub fn run(config: Rc<RefCell<NeedleConfig>>) -> Result<()> {
-    let event_loop = EventLoop::new()?;
+/// Run needle.
+/// With `listen`, commands sent from other instances are received while running.
+pub fn run(
+    config: Rc<RefCell<NeedleConfig>>,
+    app_config: Rc<RefCell<AppConfig>>,
+    listen: bool,
+) -> Result<()> {
+    let event_loop = EventLoop::<Request>::with_user_event().build()?;
     let mut app = Needle::default();
+    // Socket is closed when needle exits
+    let server = if listen {
+        ipc::listen(event_loop.create_proxy())
+            .map_err(|e| log::error!("Failed to listen for commands: {e}"))
+            .ok()
+    } else {
+        None
+    };
 
-    app.set_config(config)?;
+    app.set_config(config, app_config)?;
+    if let Some(server) = server.as_ref() {
+        app.set_events(server.events());
+    }
     event_loop.set_control_flow(ControlFlow::Poll);
     match event_loop.run_app(&mut app) {
         Ok(_) => Ok(()),
@@ -0,0 +1,476 @@
+// Copyright 2025 Kensuke Saito
+// SPDX-License-Identifier: MIT
+
+use crate::{
+    color::normalize_colors,
+    fonts::{font_path, register_fonts},
+    format::{format_timer, validate_clock_format},
+    AlarmAction, AppConfig, NextTimer, PlaylistSegmentMode, PomodoroPhase, Profiles,
+};
+use anyhow::{anyhow, bail, Result};
+use needle_core::NeedleConfig;
+use serde::de::DeserializeOwned;
+use std::{
+    fmt::{self, Display, Formatter},
+    fs,
+    ops::Range,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+use toml::de::{DeTable, DeValue};
+
+const CONFIG_FILE: &str = "config.toml";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Severity {
+    Error,
+    Warning,
+}
+
+/// Problem found in config file
+#[derive(Debug)]
+struct Diagnostic {
+    severity: Severity,
+    message: String,
+    span: Option<Range<usize>>,
+}
+
+/// Semantic checks of config file.
+/// Problems are located with the spans of the parsed document.
+struct Checker<'a, 'i> {
+    table: &'a DeTable<'i>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+/// Parse and validate config file, printing problems with file, line and column.
+/// Fails if any error has been found.
+pub fn check_config(path: Option<&str>) -> Result<()> {
+    let path = match path {
+        Some(path) => PathBuf::from(path),
+        None => NeedleConfig::config_path(false, Some(CONFIG_FILE))?,
+    };
+    let source = fs::read_to_string(&path).map_err(|e| anyhow!("{}: {e}", path.display()))?;
+    let diagnostics = diagnose(&source, &path);
+    let count = |severity| {
+        diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.severity == severity)
+            .count()
+    };
+    let (errors, warnings) = (count(Severity::Error), count(Severity::Warning));
+
+    diagnostics.iter().for_each(|diagnostic| {
+        let (line, column) = diagnostic
+            .span
+            .as_ref()
+            .map(|span| line_column(&source, span.start))
+            .unwrap_or((1, 1));
+
+        println!(
+            "{}:{line}:{column}: {}: {}",
+            path.display(),
+            diagnostic.severity,
+            diagnostic.message
+        );
+    });
+    if errors > 0 {
+        bail!(
+            "{}: {errors} error(s), {warnings} warning(s)",
+            path.display()
+        );
+    }
+    println!("{}: OK ({warnings} warning(s))", path.display());
+
+    Ok(())
+}
+
+fn diagnose(source: &str, path: &Path) -> Vec<Diagnostic> {
+    let table = match DeTable::parse(source) {
+        Ok(table) => table,
+        Err(e) => return vec![Diagnostic::error(e.message(), e.span())],
+    };
+    let mut normalized = match source.parse::<toml::Table>() {
+        Ok(table) => table,
+        Err(e) => return vec![Diagnostic::error(e.message(), e.span())],
+    };
+
+    if let Err(e) = normalize_colors(&mut normalized) {
+        return vec![Diagnostic::error(&e.to_string(), None)];
+    }
+
+    let config = match deserialize::<NeedleConfig>(source, &normalized) {
+        Ok(config) => config,
+        Err(diagnostic) => return vec![diagnostic],
+    };
+    let app_config = match deserialize::<AppConfig>(source, &normalized) {
+        Ok(config) => config,
+        Err(diagnostic) => return vec![diagnostic],
+    };
+    let mut checker = Checker {
+        table: table.get_ref(),
+        diagnostics: Vec::new(),
+    };
+
+    checker.colors(&config, &app_config);
+    checker.fonts(&config, &app_config);
+    checker.positions(&config, &app_config);
+    checker.frame_limit(&config);
+    checker.formats(&app_config);
+    checker.shaders(&app_config);
+    checker.windows(&app_config, path);
+    checker.pomodoro(&app_config);
+    checker.playlist(&app_config);
+    checker.alarms(&app_config);
+    checker.finish(&app_config);
+
+    checker.diagnostics
+}
+
+/// Deserialize config with hex colors accepted.
+/// Errors are located in the original source.
+fn deserialize<T: DeserializeOwned>(
+    source: &str,
+    normalized: &toml::Table,
+) -> Result<T, Diagnostic> {
+    toml::from_str::<T>(source).or_else(|e| {
+        normalized
+            .clone()
+            .try_into::<T>()
+            .map_err(|_| Diagnostic::error(e.message(), e.span()))
+    })
+}
+
+impl Checker<'_, '_> {
+    fn colors(&mut self, config: &NeedleConfig, app_config: &AppConfig) {
+        self.color_range(&["background_color"], &config.background_color);
+        app_config
+            .background
+            .gradient
+            .stops
+            .iter()
+            .enumerate()
+            .for_each(|(i, stop)| {
+                let i = i.to_string();
+
+                self.color_range(
+                    &["background", "gradient", "stops", &i, "color"],
+                    &stop.color,
+                );
+            });
+        self.color_range(
+            &["background", "panel", "border_color"],
+            &app_config.background.panel.border_color,
+        );
+    }
+
+    /// Colors stored as float must be in range of 0.0 - 1.0
+    fn color_range(&mut self, path: &[&str], color: &[f32]) {
+        color.iter().enumerate().for_each(|(i, value)| {
+            if !(0.0..=1.0).contains(value) {
+                let i = i.to_string();
+                let path = [path, &[i.as_str()]].concat();
+
+                self.error(
+                    &path,
+                    format!("color component {value} is out of range (0.0 - 1.0)"),
+                );
+            }
+        });
+    }
+
+    fn fonts(&mut self, config: &NeedleConfig, app_config: &AppConfig) {
+        let fonts = [
+            (&["time", "font"][..], config.time.font.as_ref()),
+            (&["subtitle", "font"][..], app_config.subtitle.font.as_ref()),
+            (&["fonts", "fps"][..], app_config.fonts.fps.as_ref()),
+        ];
+
+        app_config
+            .fonts
+            .files
+            .iter()
+            .enumerate()
+            .for_each(|(i, file)| {
+                if !font_path(file).is_ok_and(|path| path.is_file()) {
+                    self.error(
+                        &["fonts", "files", &i.to_string()],
+                        format!("font file \"{file}\" does not exist"),
+                    );
+                }
+            });
+        // Fonts in font files are available as system fonts while registered.
+        // Registration is undone on drop, leaving no files behind.
+        let registered = register_fonts(&app_config.fonts.files)
+            .inspect_err(|e| self.warning(&["fonts"], format!("failed to load font files: {e}")));
+        let available = font_loader::system_fonts::query_all();
+
+        drop(registered);
+
+        fonts.iter().for_each(|(path, font)| {
+            if let Some(font) = font {
+                if !available.contains(font) {
+                    self.error(path, format!("font \"{font}\" is not installed"));
+                }
+            }
+        });
+    }
+
+    fn positions(&mut self, config: &NeedleConfig, app_config: &AppConfig) {
+        let layout = &app_config.layout;
+
+        if config.fps.enable
+            && config.time.config.position == config.fps.config.position
+            && layout.time == layout.fps
+        {
+            self.error(
+                &["fps", "config", "position"],
+                "time and FPS share the same position and offset".to_string(),
+            );
+        }
+    }
+
+    fn frame_limit(&mut self, config: &NeedleConfig) {
+        if config.fps.frame_limit == 0 {
+            self.error(
+                &["fps", "frame_limit"],
+                "frame limit must be greater than 0".to_string(),
+            );
+        }
+    }
+
+    fn formats(&mut self, app_config: &AppConfig) {
+        let custom_format = &app_config.custom_format;
+
+        if let Err(e) = validate_clock_format(&custom_format.clock) {
+            self.error(&["custom_format", "clock"], e.to_string());
+        }
+        if let Err(e) = format_timer(&custom_format.timer, Duration::ZERO) {
+            self.error(&["custom_format", "timer"], e.to_string());
+        }
+        if let Err(e) = validate_clock_format(&app_config.subtitle.date_format) {
+            self.error(&["subtitle", "date_format"], e.to_string());
+        }
+    }
+
+    fn shaders(&mut self, app_config: &AppConfig) {
+        // Built-in shaders are generated on launch, so only the custom shader is checked
+        let shader = &app_config.background.shader;
+
+        if shader.enable {
+            let source = match PathBuf::from(&shader.path) {
+                source if source.is_absolute() => Some(source),
+                _ => NeedleConfig::config_path(false, Some(&shader.path)).ok(),
+            };
+
+            if !source.as_ref().is_some_and(|source| source.exists()) {
+                self.error(
+                    &["background", "shader", "path"],
+                    format!("shader \"{}\" does not exist", shader.path),
+                );
+            }
+        }
+    }
+
+    fn windows(&mut self, app_config: &AppConfig, path: &Path) {
+        if app_config.windows.is_empty() {
+            return;
+        }
+
+        let profiles = match Profiles::read(path) {
+            Ok(profiles) => profiles,
+            Err(e) => {
+                self.error(&["windows"], format!("failed to read profiles: {e}"));
+
+                return;
+            }
+        };
+
+        app_config
+            .windows
+            .iter()
+            .enumerate()
+            .filter(|(_, window)| profiles.get(&window.profile).is_err())
+            .for_each(|(i, window)| {
+                self.error(
+                    &["windows", &i.to_string(), "profile"],
+                    format!("profile \"{}\" does not exist", window.profile),
+                );
+            });
+    }
+
+    fn pomodoro(&mut self, app_config: &AppConfig) {
+        let pomodoro = &app_config.pomodoro;
+
+        if pomodoro.rounds == 0 {
+            self.error(
+                &["pomodoro", "rounds"],
+                "rounds must be greater than 0".to_string(),
+            );
+        }
+        for (phase, key) in PomodoroPhase::ALL
+            .iter()
+            .zip(["work", "short_break", "long_break"])
+        {
+            if pomodoro.phase(*phase).duration.is_zero() {
+                self.error(
+                    &["pomodoro", key, "duration"],
+                    "duration must be greater than 0".to_string(),
+                );
+            }
+        }
+    }
+
+    fn playlist(&mut self, app_config: &AppConfig) {
+        let playlist = &app_config.playlist;
+
+        if playlist.enable && app_config.pomodoro.enable {
+            self.warning(
+                &["playlist", "enable"],
+                "playlist does not run while Pomodoro mode is enabled".to_string(),
+            );
+        }
+        if playlist.enable && playlist.segments.is_empty() {
+            self.warning(
+                &["playlist", "enable"],
+                "playlist is enabled but has no segments".to_string(),
+            );
+        }
+        playlist
+            .segments
+            .iter()
+            .enumerate()
+            .for_each(|(i, segment)| {
+                let i = i.to_string();
+
+                match segment.duration {
+                    None if segment.mode == PlaylistSegmentMode::Countdown => self.error(
+                        &["playlist", "segments", &i, "mode"],
+                        "countdown segment requires duration".to_string(),
+                    ),
+                    Some(duration) if duration.is_zero() => self.error(
+                        &["playlist", "segments", &i, "duration"],
+                        "duration must be greater than 0".to_string(),
+                    ),
+                    _ => (),
+                }
+                if let Some(color) = segment.background_color.as_ref() {
+                    self.color_range(&["playlist", "segments", &i, "background_color"], color);
+                }
+            });
+    }
+
+    fn alarms(&mut self, app_config: &AppConfig) {
+        app_config.alarms.iter().enumerate().for_each(|(i, alarm)| {
+            let i = i.to_string();
+
+            if alarm.duration.is_zero() {
+                self.warning(
+                    &["alarms", &i, "duration"],
+                    "alarm with duration of 0 is not shown".to_string(),
+                );
+            }
+            match &alarm.action {
+                AlarmAction::Countdown(duration) if duration.is_zero() => self.error(
+                    &["alarms", &i, "action"],
+                    "countdown duration must be greater than 0".to_string(),
+                ),
+                AlarmAction::Command(command) if command.trim().is_empty() => {
+                    self.error(&["alarms", &i, "action"], "command is empty".to_string())
+                }
+                _ => (),
+            }
+            if let Some(color) = alarm.background_color.as_ref() {
+                self.color_range(&["alarms", &i, "background_color"], color);
+            }
+        });
+    }
+
+    fn finish(&mut self, app_config: &AppConfig) {
+        let finish = &app_config.timer.finish;
+
+        if let NextTimer::Countdown(duration) = finish.start {
+            if duration.is_zero() {
+                self.error(
+                    &["timer", "finish", "start"],
+                    "countdown duration must be greater than 0".to_string(),
+                );
+            }
+        }
+        if let Some(color) = finish.background_color.as_ref() {
+            self.color_range(&["timer", "finish", "background_color"], color);
+        }
+    }
+
+    fn error(&mut self, path: &[&str], message: String) {
+        self.diagnostics.push(Diagnostic {
+            severity: Severity::Error,
+            message,
+            span: self.span(path),
+        });
+    }
+
+    fn warning(&mut self, path: &[&str], message: String) {
+        self.diagnostics.push(Diagnostic {
+            severity: Severity::Warning,
+            message,
+            span: self.span(path),
+        });
+    }
+
+    /// Span of value at `path` (array elements are indexed by number).
+    /// Falls back to the deepest value found if the path is incomplete.
+    fn span(&self, path: &[&str]) -> Option<Range<usize>> {
+        let (first, rest) = path.split_first()?;
+        let mut value = self.table.get(*first)?;
+
+        for key in rest {
+            let next = match value.get_ref() {
+                DeValue::Table(table) => table.get(*key),
+                DeValue::Array(array) => key.parse::<usize>().ok().and_then(|i| array.get(i)),
+                _ => None,
+            };
+
+            match next {
+                Some(next) => value = next,
+                None => break,
+            }
+        }
+
+        Some(value.span())
+    }
+}
+
+impl Diagnostic {
+    fn error(message: &str, span: Option<Range<usize>>) -> Self {
+        Self {
+            severity: Severity::Error,
+            message: message.to_string(),
+            span,
+        }
+    }
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            Self::Error => "error",
+            Self::Warning => "warning",
+        };
+
+        write!(f, "{msg}")
+    }
+}
+
+/// Line and column (1-based) of byte offset in source
+fn line_column(source: &str, offset: usize) -> (usize, usize) {
+    let before = &source[..offset.min(source.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before
+        .rsplit('\n')
+        .next()
+        .map(|line| line.chars().count())
+        .unwrap_or(0)
+        + 1;
+
+    (line, column)
+}
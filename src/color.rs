@@ -0,0 +1,411 @@
+// Copyright 2025 Kensuke Saito
+// SPDX-License-Identifier: MIT
+
+use anyhow::{anyhow, bail, Result};
+
+/// Colors stored as `[u8; 3]`.
+/// Alpha of hex colors goes to the key in `TEXT_ALPHA` if there is one, and is dropped otherwise.
+const RGB_COLORS: [&[&str]; 11] = [
+    &["time", "config", "color"],
+    &["fps", "config", "color"],
+    &["subtitle", "color"],
+    &["effects", "time", "outline", "color"],
+    &["effects", "time", "shadow", "color"],
+    &["effects", "fps", "outline", "color"],
+    &["effects", "fps", "shadow", "color"],
+    &["pomodoro", "work", "color"],
+    &["pomodoro", "short_break", "color"],
+    &["pomodoro", "long_break", "color"],
+    &["timer", "finish", "color"],
+];
+/// Alpha of text colors (`[u8; 3]` in `NeedleConfig`)
+const TEXT_ALPHA: [(&[&str], &[&str]); 2] = [
+    (&["time", "config", "color"], &["text_alpha", "time"]),
+    (&["fps", "config", "color"], &["text_alpha", "fps"]),
+];
+/// Colors stored as `[f32; 4]`
+const RGBA_COLORS: [&[&str]; 6] = [
+    &["background_color"],
+    &["background", "panel", "border_color"],
+    &["pomodoro", "work", "background_color"],
+    &["pomodoro", "short_break", "background_color"],
+    &["pomodoro", "long_break", "background_color"],
+    &["timer", "finish", "background_color"],
+];
+const GRADIENT_STOPS: [&str; 3] = ["background", "gradient", "stops"];
+/// Arrays of tables with optional `color` (`[u8; 3]`) and `background_color` (`[f32; 4]`)
+const STYLED_TABLES: [&[&str]; 2] = [&["playlist", "segments"], &["alarms"]];
+
+/// Parse `#RRGGBB` or `#RRGGBBAA` (alpha defaults to 255)
+pub fn parse_hex(hex: &str) -> Result<[u8; 4]> {
+    let digits = hex.trim().strip_prefix('#').unwrap_or(hex.trim());
+
+    if !(digits.len() == 6 || digits.len() == 8)
+        || !digits.chars().all(|digit| digit.is_ascii_hexdigit())
+    {
+        bail!("Invalid color \"{hex}\" (expected #RRGGBB or #RRGGBBAA)");
+    }
+
+    let mut rgba = [u8::MAX; 4];
+
+    for (i, channel) in rgba.iter_mut().take(digits.len() / 2).enumerate() {
+        *channel = u8::from_str_radix(&digits[i * 2..i * 2 + 2], 16)?;
+    }
+
+    Ok(rgba)
+}
+
+/// Format color (0.0 - 1.0) as `#RRGGBBAA`, or `#RRGGBB` without alpha
+pub fn to_hex(color: &[f32; 4], alpha: bool) -> String {
+    let channels = if alpha { &color[..] } else { &color[..3] };
+
+    channels.iter().fold("#".to_string(), |hex, channel| {
+        format!(
+            "{hex}{:02X}",
+            (channel.clamp(0.0, 1.0) * 255.0).round() as u8
+        )
+    })
+}
+
+/// Text color blended towards the background color by `1.0 - alpha`.
+/// Text is drawn with opaque colors, so this dims the text rather than making it translucent
+/// (text on a transparent background fades to black).
+pub fn blend_text_color(color: [u8; 3], alpha: f32, background_color: &[f32]) -> [u8; 3] {
+    let alpha = alpha.clamp(0.0, 1.0);
+
+    std::array::from_fn(|i| {
+        let background = background_color[i] * background_color[3] * 255.0;
+
+        (color[i] as f32 * alpha + background * (1.0 - alpha)).round() as u8
+    })
+}
+
+/// Keep text colors written as hex strings in `raw` (the config table before
+/// `normalize_colors`) as hex when saving, with alpha taken from `text_alpha`.
+/// Otherwise the alpha of the unchanged `#RRGGBBAA` string overrides `text_alpha` on next load.
+/// `old` and `new` are the normalized tables of the saved and current settings.
+pub fn keep_hex_text_colors(raw: &toml::Table, old: &mut toml::Table, new: &mut toml::Table) {
+    for (color, alpha) in TEXT_ALPHA {
+        let Some(toml::Value::String(hex)) = get(raw, color) else {
+            continue;
+        };
+        let changed = get(old, color) != get(new, color) || get(old, alpha) != get(new, alpha);
+        let new_hex = match (get(new, color), get(new, alpha)) {
+            (Some(toml::Value::Array(rgb)), Some(alpha)) if changed => {
+                let channel = |value: Option<&toml::Value>| {
+                    value
+                        .and_then(|value| value.as_integer())
+                        .map_or(0.0, |value| value as f32 / 255.0)
+                };
+                let alpha = alpha.as_float().unwrap_or(1.0) as f32;
+                let rgba = std::array::from_fn(|i| match i {
+                    3 => alpha,
+                    _ => channel(rgb.get(i)),
+                });
+
+                to_hex(&rgba, true)
+            }
+            _ => hex.clone(),
+        };
+
+        insert(old, color, toml::Value::String(hex.clone()));
+        insert(new, color, toml::Value::String(new_hex));
+    }
+}
+
+/// Replace hex color strings in config table with the arrays expected by the config structs
+pub fn normalize_colors(table: &mut toml::Table) -> Result<()> {
+    for (color, alpha) in TEXT_ALPHA {
+        if let Some(toml::Value::String(hex)) = get(table, color) {
+            let rgba = parse_color(color, hex)?;
+
+            insert(table, alpha, unit_value(rgba[3]));
+        }
+    }
+    for color in RGB_COLORS {
+        if let Some(value) = get_mut(table, color) {
+            normalize_rgb(color, value)?;
+        }
+    }
+    for color in RGBA_COLORS {
+        if let Some(value) = get_mut(table, color) {
+            normalize_rgba(color, value)?;
+        }
+    }
+    if let Some(toml::Value::Array(stops)) = get_mut(table, &GRADIENT_STOPS) {
+        for (i, stop) in stops.iter_mut().enumerate() {
+            if let Some(color) = stop.as_table_mut().and_then(|stop| stop.get_mut("color")) {
+                let i = i.to_string();
+
+                normalize_rgba(
+                    &[&GRADIENT_STOPS[..], &[i.as_str(), "color"]].concat(),
+                    color,
+                )?;
+            }
+        }
+    }
+
+    for tables in STYLED_TABLES {
+        let Some(toml::Value::Array(entries)) = get_mut(table, tables) else {
+            continue;
+        };
+
+        for (i, entry) in entries.iter_mut().enumerate() {
+            let Some(entry) = entry.as_table_mut() else {
+                continue;
+            };
+            let i = i.to_string();
+            let path = [tables, &[i.as_str()]].concat();
+
+            if let Some(color) = entry.get_mut("color") {
+                normalize_rgb(&[&path[..], &["color"]].concat(), color)?;
+            }
+            if let Some(color) = entry.get_mut("background_color") {
+                normalize_rgba(&[&path[..], &["background_color"]].concat(), color)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn normalize_rgb(path: &[&str], value: &mut toml::Value) -> Result<()> {
+    if let toml::Value::String(hex) = value {
+        let rgba = parse_color(path, hex)?;
+
+        *value = toml::Value::Array(
+            rgba[..3]
+                .iter()
+                .map(|channel| toml::Value::Integer((*channel).into()))
+                .collect(),
+        );
+    }
+
+    Ok(())
+}
+
+fn normalize_rgba(path: &[&str], value: &mut toml::Value) -> Result<()> {
+    if let toml::Value::String(hex) = value {
+        let rgba = parse_color(path, hex)?;
+
+        *value = toml::Value::Array(rgba.iter().map(|channel| unit_value(*channel)).collect());
+    }
+
+    Ok(())
+}
+
+/// Parse hex color, naming the key on error
+fn parse_color(path: &[&str], hex: &str) -> Result<[u8; 4]> {
+    parse_hex(hex).map_err(|e| anyhow!("{}: {e}", path.join(".")))
+}
+
+/// Channel as float (0.0 - 1.0) written the way `f32` values are saved
+fn unit_value(channel: u8) -> toml::Value {
+    let value = (channel as f32 / 255.0).to_string();
+
+    toml::Value::Float(value.parse().unwrap_or_default())
+}
+
+fn get<'a>(table: &'a toml::Table, path: &[&str]) -> Option<&'a toml::Value> {
+    let (last, parents) = path.split_last()?;
+
+    parents
+        .iter()
+        .try_fold(table, |table, key| table.get(*key)?.as_table())?
+        .get(*last)
+}
+
+fn get_mut<'a>(table: &'a mut toml::Table, path: &[&str]) -> Option<&'a mut toml::Value> {
+    let (last, parents) = path.split_last()?;
+
+    parents
+        .iter()
+        .try_fold(table, |table, key| table.get_mut(*key)?.as_table_mut())?
+        .get_mut(*last)
+}
+
+/// Insert value at `path`, creating missing tables
+fn insert(table: &mut toml::Table, path: &[&str], value: toml::Value) {
+    let Some((last, parents)) = path.split_last() else {
+        return;
+    };
+    let mut table = table;
+
+    for key in parents {
+        let entry = table
+            .entry(key.to_string())
+            .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+
+        match entry.as_table_mut() {
+            Some(next) => table = next,
+            None => return,
+        }
+    }
+    table.insert(last.to_string(), value);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table(source: &str) -> toml::Table {
+        source.parse().unwrap()
+    }
+
+    #[test]
+    fn parse_valid_hex() {
+        let cases = [
+            ("#FF8000", [255, 128, 0, 255]),
+            ("#ff800040", [255, 128, 0, 64]),
+            ("ff8000", [255, 128, 0, 255]),
+            (" #000000 ", [0, 0, 0, 255]),
+        ];
+
+        for (hex, rgba) in cases {
+            assert_eq!(parse_hex(hex).unwrap(), rgba, "{hex}");
+        }
+    }
+
+    #[test]
+    fn blend_text_colors() {
+        let cases = [
+            ([255, 128, 0], 1.0, [0.0, 0.0, 1.0, 1.0], [255, 128, 0]),
+            ([255, 128, 0], 0.0, [0.0, 0.0, 1.0, 1.0], [0, 0, 255]),
+            ([255, 255, 255], 0.5, [0.0, 0.0, 0.0, 1.0], [128, 128, 128]),
+            ([200, 100, 0], 1.5, [1.0, 1.0, 1.0, 1.0], [200, 100, 0]),
+            // Transparent background only darkens the text (it is not made translucent)
+            ([255, 255, 255], 0.5, [1.0, 1.0, 1.0, 0.0], [128, 128, 128]),
+            ([255, 128, 0], 0.0, [0.2, 0.4, 0.6, 0.0], [0, 0, 0]),
+        ];
+
+        for (color, alpha, background, blended) in cases {
+            assert_eq!(
+                blend_text_color(color, alpha, &background),
+                blended,
+                "{color:?} {alpha} {background:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn parse_invalid_hex() {
+        let cases = [
+            "",
+            "#",
+            "#FFF",
+            "#FF80000",
+            "#FF8000400",
+            "#GG8000",
+            "#+F8000",
+        ];
+
+        for hex in cases {
+            assert!(parse_hex(hex).is_err(), "{hex}");
+        }
+    }
+
+    #[test]
+    fn normalize_hex_colors() {
+        let mut colors = table(
+            r##"
+            background_color = "#FF000080"
+
+            [time.config]
+            color = "#00FF0040"
+
+            [fps.config]
+            color = [1, 2, 3]
+
+            [subtitle]
+            color = "#0000FF80"
+
+            [[background.gradient.stops]]
+            color = "#FFFFFF"
+
+            [[alarms]]
+            color = "#102030"
+            background_color = "#00000000"
+            "##,
+        );
+        let expected = table(
+            r#"
+            background_color = [1.0, 0.0, 0.0, 0.5019608]
+
+            [time.config]
+            color = [0, 255, 0]
+
+            [text_alpha]
+            time = 0.2509804
+
+            [fps.config]
+            color = [1, 2, 3]
+
+            [subtitle]
+            color = [0, 0, 255]
+
+            [[background.gradient.stops]]
+            color = [1.0, 1.0, 1.0, 1.0]
+
+            [[alarms]]
+            color = [16, 32, 48]
+            background_color = [0.0, 0.0, 0.0, 0.0]
+            "#,
+        );
+
+        normalize_colors(&mut colors).unwrap();
+        assert_eq!(colors, expected);
+    }
+
+    #[test]
+    fn reject_invalid_colors() {
+        let cases = [
+            ("[time.config]\ncolor = \"#12345\"", "time.config.color"),
+            ("[[alarms]]\n[[alarms]]\ncolor = \"red\"", "alarms.1.color"),
+        ];
+
+        for (source, path) in cases {
+            let e = normalize_colors(&mut table(source)).unwrap_err();
+
+            assert!(e.to_string().starts_with(path), "{e}");
+        }
+    }
+
+    #[test]
+    fn keep_hex_text_colors_with_alpha() {
+        let raw = table("[time.config]\ncolor = \"#ffffffc0\"\n[fps.config]\ncolor = [0, 0, 0]");
+        let mut saved = raw.clone();
+
+        normalize_colors(&mut saved).unwrap();
+
+        let cases = [
+            // Unchanged colors keep their original string
+            (saved.clone(), "#ffffffc0"),
+            // Changed alpha is written to hex string
+            (
+                table(
+                    "[time.config]\ncolor = [255, 255, 255]\n[fps.config]\ncolor = [0, 0, 0]\n\
+                     [text_alpha]\ntime = 0.5",
+                ),
+                "#FFFFFF80",
+            ),
+        ];
+
+        for (current, hex) in cases {
+            let (mut old, mut new) = (saved.clone(), current);
+
+            keep_hex_text_colors(&raw, &mut old, &mut new);
+            assert_eq!(
+                get(&old, &["time", "config", "color"]).unwrap().as_str(),
+                Some("#ffffffc0")
+            );
+            assert_eq!(
+                get(&new, &["time", "config", "color"]).unwrap().as_str(),
+                Some(hex)
+            );
+            assert_eq!(
+                get(&new, &["fps", "config", "color"]),
+                get(&saved, &["fps", "config", "color"])
+            );
+        }
+    }
+}
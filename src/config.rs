@@ -0,0 +1,1377 @@
+// Copyright 2025 Kensuke Saito
+// SPDX-License-Identifier: MIT
+
+use crate::{
+    color::{keep_hex_text_colors, normalize_colors},
+    document::update_document,
+    format::format_clock,
+    geometry::Geometry,
+    i18n::Language,
+    migrate::CONFIG_VERSION,
+    Profiles,
+};
+use anyhow::{anyhow, Result};
+use chrono::NaiveTime;
+use needle_core::{NeedleConfig, OpMode};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::{
+    fmt::{self, Display, Formatter},
+    fs, mem,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+use winit::dpi::PhysicalSize;
+
+/// Application specific settings stored next to `NeedleConfig` in the same config file
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppConfig {
+    pub timer: TimerConfig,
+    pub custom_format: CustomFormatConfig,
+    pub subtitle: SubtitleConfig,
+    pub layout: LayoutConfig,
+    pub effects: EffectsConfig,
+    pub background: BackgroundConfig,
+    pub text_alpha: TextAlphaConfig,
+    /// Language of UI and CLI help
+    pub language: Language,
+    pub fonts: FontsConfig,
+    pub window: WindowConfig,
+    pub overlay: OverlayConfig,
+    pub pomodoro: PomodoroConfig,
+    pub playlist: PlaylistConfig,
+    /// Alarms at times of day (checked in clock mode)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub alarms: Vec<AlarmConfig>,
+    /// Additional clock windows opened on launch
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub windows: Vec<ClockWindowConfig>,
+    #[serde(skip)]
+    pub profiles: Profiles,
+    /// Settings as last read/saved to detect unsaved changes
+    #[serde(skip)]
+    saved: Option<toml::Table>,
+    #[serde(skip)]
+    path: PathBuf,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TimerConfig {
+    /// Countdown duration to start with (e.g. "1h30m", "90:00", "1:30:00", "45s")
+    #[serde(
+        with = "crate::duration::serde_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub countdown: Option<Duration>,
+    /// Sub-second digits shown by count up/countdown timers
+    pub precision: TimerPrecision,
+    /// Actions when countdown timer reaches zero
+    pub finish: FinishConfig,
+}
+
+/// Actions run when countdown timer reaches zero (except in Pomodoro mode and playlists)
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FinishConfig {
+    /// Shown in place of the time (time is kept if empty)
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub message: String,
+    /// Text color after countdown has finished
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<[u8; 3]>,
+    /// Background color after countdown has finished
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub background_color: Option<[f32; 4]>,
+    pub blink: bool,
+    /// How long message and colors are shown before `start`.
+    /// If unset, they are shown until timer is reset, or briefly when followed by `start`.
+    #[serde(
+        with = "crate::duration::serde_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub duration: Option<Duration>,
+    /// Timer started after countdown has finished
+    pub start: NextTimer,
+    /// Run with the shell, with timer described in `NEEDLE_TIMER_*` environment variables
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub command: String,
+    /// File written when countdown has finished (relative to the config directory)
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub marker_file: String,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NextTimer {
+    #[default]
+    None,
+    /// Countdown with the duration
+    Countdown(#[serde(with = "crate::duration::serde_required")] Duration),
+    CountUp,
+    /// Same countdown again
+    Repeat,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimerPrecision {
+    #[default]
+    Seconds,
+    Tenths,
+    Hundredths,
+    Milliseconds,
+}
+
+/// Custom format strings overriding `time.format`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CustomFormatConfig {
+    pub enable: bool,
+    /// chrono strftime pattern for clock mode (e.g. "%a %d %b %H:%M")
+    pub clock: String,
+    /// Token pattern for timer modes ({d}, {h}, {hh}, {m}, {mm}, {s}, {ss}, {f}, {ff}, {ms})
+    pub timer: String,
+}
+
+impl Default for CustomFormatConfig {
+    fn default() -> Self {
+        Self {
+            enable: false,
+            clock: "%H:%M:%S".to_string(),
+            timer: "{hh}:{mm}:{ss}".to_string(),
+        }
+    }
+}
+
+/// Secondary text line rendered under/above the clock
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SubtitleConfig {
+    pub enable: bool,
+    pub content: SubtitleContent,
+    /// chrono strftime pattern for date
+    pub date_format: String,
+    pub label: String,
+    pub placement: SubtitlePlacement,
+    pub font: Option<String>,
+    pub scale: f32,
+    pub color: [u8; 3],
+    /// Gap between clock and subtitle in pixels
+    pub spacing: f32,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SubtitleContent {
+    #[default]
+    Date,
+    Label,
+    DateAndLabel,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SubtitlePlacement {
+    #[default]
+    Below,
+    Above,
+}
+
+impl SubtitleConfig {
+    /// Text of subtitle.
+    /// Date is left empty if `date_format` is invalid.
+    pub fn text(&self) -> String {
+        let date = || format_clock(&self.date_format).unwrap_or_default();
+
+        match self.content {
+            SubtitleContent::Date => date(),
+            SubtitleContent::Label => self.label.clone(),
+            SubtitleContent::DateAndLabel => format!("{} {}", self.label, date()),
+        }
+    }
+}
+
+impl Default for SubtitleConfig {
+    fn default() -> Self {
+        Self {
+            enable: false,
+            content: SubtitleContent::default(),
+            date_format: "%Y-%m-%d (%a)".to_string(),
+            label: String::new(),
+            placement: SubtitlePlacement::default(),
+            font: None,
+            scale: 0.5,
+            color: [255, 255, 255],
+            spacing: 5.0,
+        }
+    }
+}
+
+impl SubtitleContent {
+    pub const DATE: i8 = 0;
+    pub const MAX: i8 = 2;
+}
+
+impl From<i8> for SubtitleContent {
+    fn from(value: i8) -> Self {
+        match value {
+            1 => Self::Label,
+            2 => Self::DateAndLabel,
+            _ => Self::Date,
+        }
+    }
+}
+
+impl From<SubtitleContent> for i8 {
+    fn from(value: SubtitleContent) -> Self {
+        match value {
+            SubtitleContent::Date => 0,
+            SubtitleContent::Label => 1,
+            SubtitleContent::DateAndLabel => 2,
+        }
+    }
+}
+
+impl Display for SubtitleContent {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            Self::Date => "Date",
+            Self::Label => "Label",
+            Self::DateAndLabel => "Date and Label",
+        };
+
+        write!(f, "{msg}")
+    }
+}
+
+impl SubtitlePlacement {
+    pub const BELOW: i8 = 0;
+    pub const MAX: i8 = 1;
+}
+
+impl From<i8> for SubtitlePlacement {
+    fn from(value: i8) -> Self {
+        match value {
+            1 => Self::Above,
+            _ => Self::Below,
+        }
+    }
+}
+
+impl From<SubtitlePlacement> for i8 {
+    fn from(value: SubtitlePlacement) -> Self {
+        match value {
+            SubtitlePlacement::Below => 0,
+            SubtitlePlacement::Above => 1,
+        }
+    }
+}
+
+impl Display for SubtitlePlacement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            Self::Below => "Below",
+            Self::Above => "Above",
+        };
+
+        write!(f, "{msg}")
+    }
+}
+
+impl FinishConfig {
+    /// Time message and colors are shown before next timer when `duration` is unset
+    pub const DEFAULT_NOTICE_DURATION: Duration = Duration::from_secs(5);
+
+    /// How long the notice is shown (until timer is reset if `None`).
+    /// Without anything to show, the next timer starts right away.
+    pub fn notice_duration(&self) -> Option<Duration> {
+        let notice = !self.message.is_empty()
+            || self.color.is_some()
+            || self.background_color.is_some()
+            || self.blink;
+
+        match (self.duration, self.start) {
+            (Some(duration), _) => Some(duration),
+            (None, NextTimer::None) => None,
+            (None, _) if notice => Some(Self::DEFAULT_NOTICE_DURATION),
+            (None, _) => Some(Duration::ZERO),
+        }
+    }
+}
+
+impl NextTimer {
+    pub const NONE: i8 = 0;
+    pub const MAX: i8 = 3;
+    /// Duration of countdown selected in the menu
+    pub const DEFAULT_COUNTDOWN: Duration = Duration::from_secs(5 * 60);
+}
+
+impl From<i8> for NextTimer {
+    fn from(value: i8) -> Self {
+        match value {
+            1 => Self::Countdown(Self::DEFAULT_COUNTDOWN),
+            2 => Self::CountUp,
+            3 => Self::Repeat,
+            _ => Self::None,
+        }
+    }
+}
+
+impl From<NextTimer> for i8 {
+    fn from(value: NextTimer) -> Self {
+        match value {
+            NextTimer::None => 0,
+            NextTimer::Countdown(_) => 1,
+            NextTimer::CountUp => 2,
+            NextTimer::Repeat => 3,
+        }
+    }
+}
+
+impl Display for NextTimer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            Self::None => "None",
+            Self::Countdown(_) => "Countdown",
+            Self::CountUp => "Count Up",
+            Self::Repeat => "Repeat",
+        };
+
+        write!(f, "{msg}")
+    }
+}
+
+impl TimerPrecision {
+    pub const SECONDS: i8 = 0;
+    pub const MAX: i8 = 3;
+
+    /// Timer format (see `format_timer`) used unless custom format is enabled.
+    /// `None` leaves formatting to `TimeFormat`.
+    pub const fn format(&self) -> Option<&'static str> {
+        match self {
+            Self::Seconds => None,
+            Self::Tenths => Some("{hh}:{mm}:{ss}.{f}"),
+            Self::Hundredths => Some("{hh}:{mm}:{ss}.{ff}"),
+            Self::Milliseconds => Some("{hh}:{mm}:{ss}.{ms}"),
+        }
+    }
+
+    /// Smallest unit shown
+    pub const fn unit(&self) -> Duration {
+        match self {
+            Self::Seconds => Duration::from_secs(1),
+            Self::Tenths => Duration::from_millis(100),
+            Self::Hundredths => Duration::from_millis(10),
+            Self::Milliseconds => Duration::from_millis(1),
+        }
+    }
+}
+
+impl From<i8> for TimerPrecision {
+    fn from(value: i8) -> Self {
+        match value {
+            1 => Self::Tenths,
+            2 => Self::Hundredths,
+            3 => Self::Milliseconds,
+            _ => Self::Seconds,
+        }
+    }
+}
+
+impl From<TimerPrecision> for i8 {
+    fn from(value: TimerPrecision) -> Self {
+        match value {
+            TimerPrecision::Seconds => 0,
+            TimerPrecision::Tenths => 1,
+            TimerPrecision::Hundredths => 2,
+            TimerPrecision::Milliseconds => 3,
+        }
+    }
+}
+
+impl Display for TimerPrecision {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            Self::Seconds => "Seconds",
+            Self::Tenths => "Tenths",
+            Self::Hundredths => "Hundredths",
+            Self::Milliseconds => "Milliseconds",
+        };
+
+        write!(f, "{msg}")
+    }
+}
+
+/// Pomodoro mode: countdowns cycling through work and break phases
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PomodoroConfig {
+    pub enable: bool,
+    /// Work rounds in a cycle (long break follows the last round)
+    pub rounds: u32,
+    /// Start next phase automatically when a phase ends
+    pub auto_start: bool,
+    #[serde(deserialize_with = "PomodoroPhaseConfig::deserialize_work")]
+    pub work: PomodoroPhaseConfig,
+    #[serde(deserialize_with = "PomodoroPhaseConfig::deserialize_short_break")]
+    pub short_break: PomodoroPhaseConfig,
+    #[serde(deserialize_with = "PomodoroPhaseConfig::deserialize_long_break")]
+    pub long_break: PomodoroPhaseConfig,
+}
+
+/// Settings of a Pomodoro phase.
+/// Values missing in config are taken from the defaults of the phase.
+#[derive(Debug, Clone, Serialize)]
+pub struct PomodoroPhaseConfig {
+    #[serde(with = "crate::duration::serde_required")]
+    pub duration: Duration,
+    /// Text color while in this phase
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<[u8; 3]>,
+    /// Background color while in this phase
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub background_color: Option<[f32; 4]>,
+}
+
+/// `PomodoroPhaseConfig` as written in config, with any value left out
+#[derive(Deserialize)]
+struct PomodoroPhaseFields {
+    #[serde(default, with = "crate::duration::serde_option")]
+    duration: Option<Duration>,
+    color: Option<[u8; 3]>,
+    background_color: Option<[f32; 4]>,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum PomodoroPhase {
+    #[default]
+    Work,
+    ShortBreak,
+    LongBreak,
+}
+
+impl PomodoroConfig {
+    pub fn phase(&self, phase: PomodoroPhase) -> &PomodoroPhaseConfig {
+        match phase {
+            PomodoroPhase::Work => &self.work,
+            PomodoroPhase::ShortBreak => &self.short_break,
+            PomodoroPhase::LongBreak => &self.long_break,
+        }
+    }
+
+    pub fn phase_mut(&mut self, phase: PomodoroPhase) -> &mut PomodoroPhaseConfig {
+        match phase {
+            PomodoroPhase::Work => &mut self.work,
+            PomodoroPhase::ShortBreak => &mut self.short_break,
+            PomodoroPhase::LongBreak => &mut self.long_break,
+        }
+    }
+}
+
+impl Default for PomodoroConfig {
+    fn default() -> Self {
+        Self {
+            enable: false,
+            rounds: 4,
+            auto_start: true,
+            work: PomodoroPhaseConfig::new(PomodoroPhase::Work),
+            short_break: PomodoroPhaseConfig::new(PomodoroPhase::ShortBreak),
+            long_break: PomodoroPhaseConfig::new(PomodoroPhase::LongBreak),
+        }
+    }
+}
+
+impl PomodoroPhaseConfig {
+    /// Default settings of `phase`
+    fn new(phase: PomodoroPhase) -> Self {
+        let minutes = match phase {
+            PomodoroPhase::Work => 25,
+            PomodoroPhase::ShortBreak => 5,
+            PomodoroPhase::LongBreak => 15,
+        };
+
+        Self {
+            duration: Duration::from_secs(minutes * 60),
+            color: None,
+            background_color: None,
+        }
+    }
+
+    fn deserialize_phase<'de, D: Deserializer<'de>>(
+        deserializer: D,
+        phase: PomodoroPhase,
+    ) -> Result<Self, D::Error> {
+        let fields = PomodoroPhaseFields::deserialize(deserializer)?;
+
+        Ok(Self {
+            duration: fields.duration.unwrap_or_else(|| Self::new(phase).duration),
+            color: fields.color,
+            background_color: fields.background_color,
+        })
+    }
+
+    fn deserialize_work<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Self::deserialize_phase(deserializer, PomodoroPhase::Work)
+    }
+
+    fn deserialize_short_break<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        Self::deserialize_phase(deserializer, PomodoroPhase::ShortBreak)
+    }
+
+    fn deserialize_long_break<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        Self::deserialize_phase(deserializer, PomodoroPhase::LongBreak)
+    }
+}
+
+impl PomodoroPhase {
+    pub const ALL: [Self; 3] = [Self::Work, Self::ShortBreak, Self::LongBreak];
+}
+
+impl Display for PomodoroPhase {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            Self::Work => "Work",
+            Self::ShortBreak => "Short Break",
+            Self::LongBreak => "Long Break",
+        };
+
+        write!(f, "{msg}")
+    }
+}
+
+/// Sequence of named timers run back to back (e.g. "Starting soon", "Intermission", "Round 1")
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PlaylistConfig {
+    pub enable: bool,
+    /// Start next segment automatically when a segment ends (otherwise wait for "SPACE")
+    pub auto_advance: bool,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub segments: Vec<PlaylistSegment>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlaylistSegment {
+    /// Shown next to the time
+    pub name: String,
+    #[serde(default)]
+    pub mode: PlaylistSegmentMode,
+    /// Length of countdown, or time count up timer ends at (runs until skipped if unset)
+    #[serde(
+        default,
+        with = "crate::duration::serde_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub duration: Option<Duration>,
+    /// Text color while in this segment
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<[u8; 3]>,
+    /// Background color while in this segment
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub background_color: Option<[f32; 4]>,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PlaylistSegmentMode {
+    #[default]
+    Countdown,
+    CountUp,
+}
+
+impl Default for PlaylistConfig {
+    fn default() -> Self {
+        Self {
+            enable: false,
+            auto_advance: true,
+            segments: Vec::new(),
+        }
+    }
+}
+
+impl PlaylistSegment {
+    /// Duration of newly added segments
+    pub const DEFAULT_DURATION: Duration = Duration::from_secs(5 * 60);
+
+    /// Timer mode of segment
+    pub fn op_mode(&self) -> OpMode {
+        match self.mode {
+            PlaylistSegmentMode::Countdown => {
+                OpMode::CountDownTimer(self.duration.unwrap_or_default())
+            }
+            PlaylistSegmentMode::CountUp => OpMode::CountUpTimer,
+        }
+    }
+}
+
+impl Default for PlaylistSegment {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            mode: PlaylistSegmentMode::Countdown,
+            duration: Some(Self::DEFAULT_DURATION),
+            color: None,
+            background_color: None,
+        }
+    }
+}
+
+impl PlaylistSegmentMode {
+    pub const COUNTDOWN: i8 = 0;
+    pub const MAX: i8 = 1;
+}
+
+impl From<i8> for PlaylistSegmentMode {
+    fn from(value: i8) -> Self {
+        match value {
+            1 => Self::CountUp,
+            _ => Self::Countdown,
+        }
+    }
+}
+
+impl From<PlaylistSegmentMode> for i8 {
+    fn from(value: PlaylistSegmentMode) -> Self {
+        match value {
+            PlaylistSegmentMode::Countdown => 0,
+            PlaylistSegmentMode::CountUp => 1,
+        }
+    }
+}
+
+impl Display for PlaylistSegmentMode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            Self::Countdown => "Countdown",
+            Self::CountUp => "Count Up",
+        };
+
+        write!(f, "{msg}")
+    }
+}
+
+/// Alarm firing at a time of day.
+/// `time` is required, while other values default when left out.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlarmConfig {
+    /// Time of day ("HH:MM" or "HH:MM:SS")
+    #[serde(with = "crate::alarm::serde_time_of_day")]
+    pub time: NaiveTime,
+    /// Days the alarm fires on (every day if empty)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub weekdays: Vec<Weekday>,
+    /// Shown next to the time while the alarm is active
+    #[serde(default)]
+    pub label: String,
+    /// How long the alarm stays active
+    #[serde(
+        default = "AlarmConfig::default_duration",
+        with = "crate::duration::serde_required"
+    )]
+    pub duration: Duration,
+    /// Blink text (and background) color while the alarm is active
+    #[serde(default = "AlarmConfig::default_flash")]
+    pub flash: bool,
+    /// Text color while the alarm is active
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<[u8; 3]>,
+    /// Background color while the alarm is active
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub background_color: Option<[f32; 4]>,
+    #[serde(default)]
+    pub action: AlarmAction,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Weekday {
+    Mon,
+    Tue,
+    Wed,
+    Thu,
+    Fri,
+    Sat,
+    Sun,
+}
+
+/// Action run when an alarm fires
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlarmAction {
+    #[default]
+    None,
+    /// Switch to countdown timer with the duration and start it
+    Countdown(#[serde(with = "crate::duration::serde_required")] Duration),
+    /// Run command with the shell (`sh -c` or `cmd /C`)
+    Command(String),
+    /// Emit event to clients of the control interface (`--send events`)
+    Event,
+}
+
+impl AlarmConfig {
+    fn default_duration() -> Duration {
+        Duration::from_secs(60)
+    }
+
+    fn default_flash() -> bool {
+        true
+    }
+}
+
+/// Offsets of text from the anchor set by `Position`.
+/// Stored in `[layout]` rather than next to `position` in `NeedleConfig`,
+/// as `NeedleConfig` is defined by needle-core and cannot gain new fields here.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LayoutConfig {
+    pub time: TextOffset,
+    pub fps: TextOffset,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TextOffset {
+    pub x: f32,
+    pub y: f32,
+    pub unit: OffsetUnit,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OffsetUnit {
+    #[default]
+    Pixel,
+    /// Percent of window size
+    Percent,
+}
+
+/// Window geometry restored on launch
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WindowConfig {
+    /// Save window geometry on exit
+    pub remember: bool,
+    /// Inner size in physical pixels
+    pub size: Option<[u32; 2]>,
+    /// Outer position in physical pixels.
+    /// Relative to `monitor` if set, otherwise in screen coordinates.
+    pub position: Option<[i32; 2]>,
+    /// Name of monitor the window was on
+    pub monitor: Option<String>,
+}
+
+impl Default for WindowConfig {
+    fn default() -> Self {
+        Self {
+            remember: true,
+            size: None,
+            position: None,
+            monitor: None,
+        }
+    }
+}
+
+impl WindowConfig {
+    /// Override geometry with the one given on command line
+    pub fn apply_geometry(&mut self, geometry: &Geometry) {
+        if let Some(size) = geometry.size {
+            self.size = Some(size);
+        }
+        if let Some(position) = geometry.position {
+            self.position = Some(position);
+            self.monitor = None;
+        }
+    }
+
+    /// Drop saved geometry unless it should be remembered,
+    /// so the window opens with the default geometry
+    pub fn drop_unremembered(&mut self) {
+        if !self.remember {
+            *self = Self {
+                remember: false,
+                size: None,
+                position: None,
+                monitor: None,
+            };
+        }
+    }
+}
+
+/// Additional clock window (`[[windows]]`) showing the settings of a profile
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClockWindowConfig {
+    /// Profile providing clock, mode, format and style of the window
+    pub profile: String,
+    /// Geometry of the window (geometry of the profile is not used)
+    #[serde(flatten)]
+    pub window: WindowConfig,
+}
+
+/// Window behavior for use as desktop overlay
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OverlayConfig {
+    pub always_on_top: bool,
+    /// Show title bar and borders
+    pub decorations: bool,
+    /// Hide window from taskbar.
+    /// Applied on launch on X11 (as utility window) and immediately on Windows.
+    pub skip_taskbar: bool,
+    /// Let mouse input pass through the window while the settings menu is closed
+    pub click_through: bool,
+}
+
+impl Default for OverlayConfig {
+    fn default() -> Self {
+        Self {
+            always_on_top: false,
+            decorations: true,
+            skip_taskbar: false,
+            click_through: false,
+        }
+    }
+}
+
+/// Font selection not covered by `NeedleConfig`
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FontsConfig {
+    /// Font of FPS text
+    pub fps: Option<String>,
+    /// List all system fonts instead of only monospace fonts
+    pub show_all: bool,
+    /// Font files (`.ttf`/`.otf`) to load in addition to `fonts/` in the config directory.
+    /// Relative paths are resolved from the config directory.
+    pub files: Vec<String>,
+}
+
+/// Blend (0.0 - 1.0) of time and FPS text color towards the background color.
+/// Not transparency: text is always drawn opaque, so on a transparent background
+/// it only gets darker. Also set by the alpha of `#RRGGBBAA` text colors.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TextAlphaConfig {
+    pub time: f32,
+    pub fps: f32,
+}
+
+impl Default for TextAlphaConfig {
+    fn default() -> Self {
+        Self {
+            time: 1.0,
+            fps: 1.0,
+        }
+    }
+}
+
+/// Outline and drop shadow of text
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EffectsConfig {
+    pub time: TextEffectConfig,
+    pub fps: TextEffectConfig,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TextEffectConfig {
+    pub outline: OutlineConfig,
+    pub shadow: ShadowConfig,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OutlineConfig {
+    pub enable: bool,
+    /// Width of outline in pixels
+    pub width: f32,
+    pub color: [u8; 3],
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ShadowConfig {
+    pub enable: bool,
+    /// Offset of shadow from text in pixels
+    pub offset: [f32; 2],
+    pub color: [u8; 3],
+}
+
+impl Default for OutlineConfig {
+    fn default() -> Self {
+        Self {
+            enable: false,
+            width: 2.0,
+            color: [0, 0, 0],
+        }
+    }
+}
+
+impl Default for ShadowConfig {
+    fn default() -> Self {
+        Self {
+            enable: false,
+            offset: [3.0, 3.0],
+            color: [0, 0, 0],
+        }
+    }
+}
+
+/// Style of background drawn behind text.
+/// Fill color of solid and panel styles is `background_color`.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BackgroundConfig {
+    pub style: BackgroundStyle,
+    pub gradient: GradientConfig,
+    pub panel: PanelConfig,
+    pub shader: CustomShaderConfig,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BackgroundStyle {
+    #[default]
+    Solid,
+    LinearGradient,
+    RadialGradient,
+    /// Rounded rectangle with border
+    Panel,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GradientConfig {
+    /// Direction of linear gradient in degrees (0: left to right, 90: top to bottom)
+    pub angle: f32,
+    pub stops: Vec<GradientStop>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct GradientStop {
+    /// Position of stop (0.0 - 1.0)
+    pub position: f32,
+    /// RGBA (0.0 - 1.0)
+    pub color: [f32; 4],
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PanelConfig {
+    /// Size of panel in percent of window size
+    pub size: [f32; 2],
+    /// Corner radius in pixels
+    pub radius: f32,
+    /// Border width in pixels
+    pub border_width: f32,
+    /// RGBA (0.0 - 1.0)
+    pub border_color: [f32; 4],
+}
+
+/// User supplied WGSL fragment shader replacing the built-in background shader
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CustomShaderConfig {
+    pub enable: bool,
+    /// Path to WGSL file (relative paths are resolved from the config directory)
+    pub path: String,
+}
+
+impl GradientConfig {
+    pub const MIN_STOPS: usize = 2;
+    pub const MAX_STOPS: usize = 8;
+
+    /// Stops sorted by position, limited to `MAX_STOPS`
+    pub fn sorted_stops(&self) -> Vec<GradientStop> {
+        let mut stops = self
+            .stops
+            .iter()
+            .take(Self::MAX_STOPS)
+            .copied()
+            .collect::<Vec<_>>();
+
+        stops.sort_by(|a, b| a.position.total_cmp(&b.position));
+
+        stops
+    }
+}
+
+impl Default for GradientConfig {
+    fn default() -> Self {
+        Self {
+            angle: 90.0,
+            stops: vec![
+                GradientStop {
+                    position: 0.0,
+                    color: [0.1, 0.1, 0.2, 1.0],
+                },
+                GradientStop {
+                    position: 1.0,
+                    color: [0.0, 0.0, 0.0, 1.0],
+                },
+            ],
+        }
+    }
+}
+
+impl Default for PanelConfig {
+    fn default() -> Self {
+        Self {
+            size: [90.0, 90.0],
+            radius: 16.0,
+            border_width: 2.0,
+            border_color: [1.0, 1.0, 1.0, 1.0],
+        }
+    }
+}
+
+impl BackgroundStyle {
+    pub const SOLID: i8 = 0;
+    pub const MAX: i8 = 3;
+}
+
+impl From<i8> for BackgroundStyle {
+    fn from(value: i8) -> Self {
+        match value {
+            1 => Self::LinearGradient,
+            2 => Self::RadialGradient,
+            3 => Self::Panel,
+            _ => Self::Solid,
+        }
+    }
+}
+
+impl From<BackgroundStyle> for i8 {
+    fn from(value: BackgroundStyle) -> Self {
+        match value {
+            BackgroundStyle::Solid => 0,
+            BackgroundStyle::LinearGradient => 1,
+            BackgroundStyle::RadialGradient => 2,
+            BackgroundStyle::Panel => 3,
+        }
+    }
+}
+
+impl Display for BackgroundStyle {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            Self::Solid => "Solid",
+            Self::LinearGradient => "Linear Gradient",
+            Self::RadialGradient => "Radial Gradient",
+            Self::Panel => "Panel",
+        };
+
+        write!(f, "{msg}")
+    }
+}
+
+impl TextOffset {
+    /// Offset in pixels for window of `size`
+    pub fn pixels(&self, size: &PhysicalSize<u32>) -> [f32; 2] {
+        match self.unit {
+            OffsetUnit::Pixel => [self.x, self.y],
+            OffsetUnit::Percent => [
+                self.x * size.width as f32 / 100.0,
+                self.y * size.height as f32 / 100.0,
+            ],
+        }
+    }
+
+    /// Move offset by `delta` pixels
+    pub fn move_by(&mut self, delta: [f32; 2], size: &PhysicalSize<u32>) {
+        match self.unit {
+            OffsetUnit::Pixel => {
+                self.x += delta[0];
+                self.y += delta[1];
+            }
+            OffsetUnit::Percent => {
+                self.x += delta[0] * 100.0 / size.width.max(1) as f32;
+                self.y += delta[1] * 100.0 / size.height.max(1) as f32;
+            }
+        }
+    }
+
+    /// Change unit of offset while keeping the offset in pixels
+    pub fn set_unit(&mut self, unit: OffsetUnit, size: &PhysicalSize<u32>) {
+        let [x, y] = self.pixels(size);
+
+        *self = Self {
+            x: 0.0,
+            y: 0.0,
+            unit,
+        };
+        self.move_by([x, y], size);
+    }
+}
+
+impl OffsetUnit {
+    pub const PIXEL: i8 = 0;
+    pub const MAX: i8 = 1;
+}
+
+impl From<i8> for OffsetUnit {
+    fn from(value: i8) -> Self {
+        match value {
+            1 => Self::Percent,
+            _ => Self::Pixel,
+        }
+    }
+}
+
+impl From<OffsetUnit> for i8 {
+    fn from(value: OffsetUnit) -> Self {
+        match value {
+            OffsetUnit::Pixel => 0,
+            OffsetUnit::Percent => 1,
+        }
+    }
+}
+
+impl Display for OffsetUnit {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            Self::Pixel => "Pixel",
+            Self::Percent => "Percent",
+        };
+
+        write!(f, "{msg}")
+    }
+}
+
+impl AppConfig {
+    const CONFIG_FILE: &'static str = "config.toml";
+
+    /// Read application specific settings from config file.
+    /// Falls back to default settings if config file does not exist.
+    pub fn read(path: Option<&str>) -> Result<Self> {
+        let path = match path {
+            Some(path) => PathBuf::from(path),
+            None => NeedleConfig::config_path(false, Some(Self::CONFIG_FILE))?,
+        };
+        let mut config = match read_table(&path)? {
+            Some(table) => table.try_into::<Self>()?,
+            None => Self::default(),
+        };
+
+        config.profiles = Profiles::read(&path)?;
+        config.path = path;
+
+        Ok(config)
+    }
+
+    /// Save settings of both `NeedleConfig` and application to config file.
+    /// Comments and formatting of the existing file are preserved.
+    pub fn save(&mut self, config: &NeedleConfig) -> Result<()> {
+        let table = self.to_table(config)?;
+        let content = if self.path.exists() {
+            let source = fs::read_to_string(&self.path)?;
+            let raw = source.parse::<toml::Table>()?;
+            let mut old = raw.clone();
+            let mut new = table.clone();
+
+            normalize_colors(&mut old)?;
+            keep_hex_text_colors(&raw, &mut old, &mut new);
+            update_document(&source, &old, &new)?
+        } else {
+            toml::to_string_pretty(&table)?
+        };
+
+        fs::write(&self.path, content)?;
+        self.saved = Some(table);
+
+        Ok(())
+    }
+
+    /// Save window geometry alone, leaving other (possibly unsaved) settings in the file untouched
+    pub fn save_window(&mut self, window: WindowConfig) -> Result<()> {
+        const WINDOW_KEY: &str = "window";
+
+        let value = toml::Value::try_from(&window)?;
+
+        self.window = window;
+        self.save_value(WINDOW_KEY, value)
+    }
+
+    /// Save geometry of additional window `index` alone (see `save_window`)
+    pub fn save_clock_window(&mut self, index: usize, window: WindowConfig) -> Result<()> {
+        const WINDOWS_KEY: &str = "windows";
+
+        let Some(clock_window) = self.windows.get_mut(index) else {
+            return Ok(());
+        };
+
+        clock_window.window = window;
+
+        let value = toml::Value::try_from(&self.windows)?;
+
+        self.save_value(WINDOWS_KEY, value)
+    }
+
+    /// Write a single top level `key` to config file and treat it as saved.
+    /// The file is left untouched if it already has the value,
+    /// and created with the saved settings if it does not exist yet.
+    fn save_value(&mut self, key: &str, value: toml::Value) -> Result<()> {
+        if let Some(saved) = self.saved.as_mut() {
+            saved.insert(key.to_string(), value.clone());
+        }
+
+        let Some(old) = read_table(&self.path)? else {
+            let saved = self
+                .saved
+                .as_ref()
+                .ok_or_else(|| anyhow!("{} does not exist", self.path.display()))?;
+
+            fs::write(&self.path, toml::to_string_pretty(saved)?)?;
+
+            return Ok(());
+        };
+
+        if old.get(key) == Some(&value) {
+            return Ok(());
+        }
+        let mut table = old.clone();
+
+        table.insert(key.to_string(), value);
+        fs::write(
+            &self.path,
+            update_document(&fs::read_to_string(&self.path)?, &old, &table)?,
+        )?;
+
+        Ok(())
+    }
+
+    /// Treat `enable` of `section` as saved with `enable`,
+    /// so leaving a mode at runtime does not count as an unsaved change
+    pub fn keep_saved_enable(&mut self, section: &str, enable: bool) {
+        const ENABLE_KEY: &str = "enable";
+
+        if let Some(toml::Value::Table(table)) =
+            self.saved.as_mut().and_then(|saved| saved.get_mut(section))
+        {
+            table.insert(ENABLE_KEY.to_string(), toml::Value::Boolean(enable));
+        }
+    }
+
+    /// Remember current settings as saved
+    pub fn mark_saved(&mut self, config: &NeedleConfig) -> Result<()> {
+        self.saved = Some(self.to_table(config)?);
+
+        Ok(())
+    }
+
+    /// Check if settings have changed since last read/save
+    pub fn is_dirty(&self, config: &NeedleConfig) -> bool {
+        self.to_table(config).ok() != self.saved
+    }
+
+    /// Discard changes since last read/save
+    pub fn revert(&mut self, config: &mut NeedleConfig) -> Result<()> {
+        if let Some(table) = self.saved.clone() {
+            self.apply_table(table, config)?;
+        }
+
+        Ok(())
+    }
+
+    /// Settings of both `NeedleConfig` and application as a single table
+    fn to_table(&self, config: &NeedleConfig) -> Result<toml::Table> {
+        let mut table = toml::Table::try_from(config)?;
+
+        table.extend(toml::Table::try_from(self)?);
+        table.insert(
+            "version".to_string(),
+            toml::Value::Integer(CONFIG_VERSION.into()),
+        );
+
+        Ok(table)
+    }
+
+    /// Replace current settings with settings in `table`
+    fn apply_table(&mut self, table: toml::Table, config: &mut NeedleConfig) -> Result<()> {
+        let app_config = table.clone().try_into::<Self>()?;
+
+        *config = table.try_into()?;
+        // Additional windows do not change with profiles
+        *self = Self {
+            windows: mem::take(&mut self.windows),
+            profiles: mem::take(&mut self.profiles),
+            saved: self.saved.take(),
+            path: mem::take(&mut self.path),
+            ..app_config
+        };
+
+        Ok(())
+    }
+
+    /// Replace current settings with profile `name`.
+    /// Settings of the profile are treated as saved.
+    pub fn switch_profile(&mut self, name: &str, config: &mut NeedleConfig) -> Result<()> {
+        let table = self.profiles.get(name)?.clone();
+
+        self.apply_table(table, config)?;
+        self.profiles.active = Some(name.to_string());
+        self.mark_saved(config)
+    }
+
+    /// Settings of additional window `window`, taken from its profile.
+    /// Other windows keep their own settings, so profiles are switched independently per window.
+    pub fn clock_window_config(&self, window: &ClockWindowConfig) -> Result<(NeedleConfig, Self)> {
+        let mut config = NeedleConfig::default();
+        let mut app_config = Self {
+            profiles: self.profiles.clone(),
+            path: self.path.clone(),
+            ..Self::default()
+        };
+
+        app_config.switch_profile(&window.profile, &mut config)?;
+        app_config.window = window.window.clone();
+        app_config.window.drop_unremembered();
+        app_config.mark_saved(&config)?;
+
+        Ok((config, app_config))
+    }
+
+    /// Store current settings as profile `name` and make it active
+    pub fn save_profile(&mut self, name: &str, config: &NeedleConfig) -> Result<()> {
+        let table = self.to_table(config)?;
+
+        self.profiles.insert(name, table.clone())?;
+        self.profiles.active = Some(name.to_string());
+        self.profiles.save()?;
+        self.saved = Some(table);
+
+        Ok(())
+    }
+}
+
+/// Read `NeedleConfig` from config file, accepting hex colors.
+/// Falls back to `NeedleConfig::read` (default settings) if config file does not exist.
+pub fn read_needle_config(path: Option<&str>) -> Result<NeedleConfig> {
+    let config_path = match path {
+        Some(path) => PathBuf::from(path),
+        None => NeedleConfig::config_path(false, Some(AppConfig::CONFIG_FILE))?,
+    };
+
+    match read_table(&config_path)? {
+        Some(table) => Ok(table.try_into()?),
+        None => Ok(NeedleConfig::read(path)?),
+    }
+}
+
+/// Parse config file with hex colors replaced by arrays
+fn read_table(path: &Path) -> Result<Option<toml::Table>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let mut table = fs::read_to_string(path)?.parse::<toml::Table>()?;
+
+    normalize_colors(&mut table)?;
+
+    Ok(Some(table))
+}
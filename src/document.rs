@@ -0,0 +1,224 @@
+// Copyright 2025 Kensuke Saito
+// SPDX-License-Identifier: MIT
+
+use anyhow::Result;
+use toml_edit::{ArrayOfTables, DocumentMut, Item, Table, TableLike};
+
+/// Write `table` into TOML `source` while preserving comments, key order and formatting.
+/// Only keys whose values differ from `old` (settings read from `source`) are rewritten,
+/// and keys missing in `table` are removed.
+pub fn update_document(source: &str, old: &toml::Table, table: &toml::Table) -> Result<String> {
+    let old = tidy_table(old);
+    let mut document = source.parse::<DocumentMut>()?;
+    let table = tidy_table(table);
+
+    update_table(document.as_table_mut(), Some(&old), &table)?;
+
+    Ok(document.to_string())
+}
+
+fn update_table(
+    document: &mut dyn TableLike,
+    old: Option<&toml::Table>,
+    new: &toml::Table,
+) -> Result<()> {
+    let removed = document
+        .iter()
+        .map(|(key, _)| key.to_string())
+        .filter(|key| !new.contains_key(key))
+        .collect::<Vec<_>>();
+
+    removed.iter().for_each(|key| {
+        document.remove(key);
+    });
+    for (key, value) in new.iter() {
+        let old_value = old.and_then(|old| old.get(key));
+
+        if old_value == Some(value) {
+            continue;
+        }
+
+        match (value, document.get_mut(key)) {
+            (toml::Value::Table(table), Some(item)) if item.is_table_like() => {
+                let old_table = old_value.and_then(|value| value.as_table());
+
+                if let Some(item) = item.as_table_like_mut() {
+                    update_table(item, old_table, table)?;
+                }
+            }
+            (toml::Value::Array(array), Some(Item::ArrayOfTables(tables)))
+                if array.iter().all(|value| value.is_table()) =>
+            {
+                let old_array = old_value.and_then(|value| value.as_array());
+
+                update_array_of_tables(tables, old_array, array)?;
+            }
+            // Values which were not tables before (or did not exist) are replaced
+            (toml::Value::Table(table), _) => {
+                let mut item = Table::new();
+
+                update_table(&mut item, None, table)?;
+                document.insert(key, Item::Table(item));
+            }
+            (value, item) => {
+                let mut new_value = value.to_string().parse::<toml_edit::Value>()?;
+
+                match item {
+                    // Replaced in place to keep comments attached to the key and value
+                    Some(item) => {
+                        if let Some(old_value) = item.as_value() {
+                            *new_value.decor_mut() = old_value.decor().clone();
+                        }
+                        *item = Item::Value(new_value);
+                    }
+                    None => {
+                        document.insert(key, Item::Value(new_value));
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn update_array_of_tables(
+    document: &mut ArrayOfTables,
+    old: Option<&Vec<toml::Value>>,
+    new: &[toml::Value],
+) -> Result<()> {
+    while document.len() > new.len() {
+        document.remove(document.len() - 1);
+    }
+    for (i, value) in new.iter().enumerate() {
+        let Some(table) = value.as_table() else {
+            continue;
+        };
+        let old_table = old
+            .and_then(|old| old.get(i))
+            .and_then(|value| value.as_table());
+
+        match document.get_mut(i) {
+            Some(item) => update_table(item, old_table, table)?,
+            None => {
+                let mut item = Table::new();
+
+                update_table(&mut item, None, table)?;
+                document.push(item);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Shorten floats serialized from `f32` (e.g. 0.10000000149011612 -> 0.1),
+/// so unchanged values compare equal and changed values stay readable.
+fn tidy(value: &toml::Value) -> toml::Value {
+    match value {
+        toml::Value::Float(float) => {
+            let short = (*float as f32).to_string().parse::<f64>().unwrap_or(*float);
+
+            toml::Value::Float(if short as f32 == *float as f32 {
+                short
+            } else {
+                *float
+            })
+        }
+        toml::Value::Array(array) => toml::Value::Array(array.iter().map(tidy).collect()),
+        toml::Value::Table(table) => toml::Value::Table(tidy_table(table)),
+        value => value.clone(),
+    }
+}
+
+fn tidy_table(table: &toml::Table) -> toml::Table {
+    table
+        .iter()
+        .map(|(key, value)| (key.clone(), tidy(value)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn update(source: &str, new: &str) -> String {
+        let old = source.parse::<toml::Table>().unwrap();
+        let new = new.parse::<toml::Table>().unwrap();
+
+        update_document(source, &old, &new).unwrap()
+    }
+
+    #[test]
+    fn keep_unchanged_document() {
+        let source = "# Clock\nversion = 3 # current\n\n[time]\nformat = \"%H:%M\"\n";
+
+        assert_eq!(update(source, source), source);
+    }
+
+    #[test]
+    fn update_values() {
+        let cases = [
+            // Comments of changed values are kept
+            (
+                "a = 1 # comment\nb = 2\n",
+                "a = 3\nb = 2\n",
+                "a = 3 # comment\nb = 2\n",
+            ),
+            // Missing keys are removed
+            ("a = 1\nb = 2\n", "a = 1\n", "a = 1\n"),
+            // New keys are appended
+            ("a = 1\n", "a = 1\nb = 2\n", "a = 1\nb = 2\n"),
+            // Nested tables are updated in place
+            (
+                "[time]\n# Format\nformat = \"%H\"\nscale = 1.0\n",
+                "[time]\nformat = \"%M\"\nscale = 1.0\n",
+                "[time]\n# Format\nformat = \"%M\"\nscale = 1.0\n",
+            ),
+            // Floats serialized from f32 are shortened
+            ("a = 0.5\n", "a = 0.10000000149011612\n", "a = 0.1\n"),
+        ];
+
+        for (source, new, expected) in cases {
+            assert_eq!(update(source, new), expected, "{source}");
+        }
+    }
+
+    #[test]
+    fn add_tables() {
+        let cases = [
+            ("a = 1\n", "a = 1\n[b]\nc = 2\n"),
+            // Value replaced by table
+            ("a = 1\nb = 2\n", "a = 1\n[b]\nc = 2\n"),
+        ];
+
+        for (source, new) in cases {
+            let updated = update(source, new);
+
+            assert_eq!(
+                updated.parse::<toml::Table>().unwrap(),
+                new.parse::<toml::Table>().unwrap(),
+                "{source}"
+            );
+        }
+    }
+
+    #[test]
+    fn update_array_of_tables() {
+        let source = "[[windows]]\nprofile = \"a\" # first\n\n[[windows]]\nprofile = \"b\"\n";
+        let cases = [
+            (
+                "[[windows]]\nprofile = \"c\"\n\n[[windows]]\nprofile = \"b\"\n",
+                "[[windows]]\nprofile = \"c\" # first\n\n[[windows]]\nprofile = \"b\"\n",
+            ),
+            (
+                "[[windows]]\nprofile = \"a\"\n",
+                "[[windows]]\nprofile = \"a\" # first\n",
+            ),
+        ];
+
+        for (new, expected) in cases {
+            assert_eq!(update(source, new), expected, "{new}");
+        }
+    }
+}
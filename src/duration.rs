@@ -0,0 +1,251 @@
+// Copyright 2025 Kensuke Saito
+// SPDX-License-Identifier: MIT
+
+use anyhow::{anyhow, bail, Result};
+use std::time::Duration;
+
+const SECONDS_PER_MINUTE: u64 = 60;
+const SECONDS_PER_HOUR: u64 = 60 * SECONDS_PER_MINUTE;
+
+/// Parse human-friendly duration.
+/// Following formats are accepted.
+///  - Unit suffixed: `1h30m`, `45s`, `1h 5m 30s`
+///  - Colon separated: `90:00` (MM:SS), `1:30:00` (HH:MM:SS)
+///  - Plain seconds: `300`
+pub fn parse_duration(input: &str) -> Result<Duration> {
+    let input = input.trim();
+
+    if input.is_empty() {
+        bail!("Duration is empty");
+    }
+
+    let secs = if input.contains(':') {
+        parse_colon_separated(input)?
+    } else if input.chars().all(|c| c.is_ascii_digit()) {
+        input.parse()?
+    } else {
+        parse_unit_suffixed(input)?
+    };
+
+    Ok(Duration::from_secs(secs))
+}
+
+/// Format duration into the unit suffixed form accepted by `parse_duration`
+pub fn format_duration(duration: &Duration) -> String {
+    let (hours, minutes, seconds) = split_duration(duration);
+    let mut formatted = String::new();
+
+    if hours > 0 {
+        formatted.push_str(&format!("{hours}h"));
+    }
+    if minutes > 0 {
+        formatted.push_str(&format!("{minutes}m"));
+    }
+    if seconds > 0 || formatted.is_empty() {
+        formatted.push_str(&format!("{seconds}s"));
+    }
+
+    formatted
+}
+
+/// Split duration into hours, minutes and seconds
+pub fn split_duration(duration: &Duration) -> (u64, u64, u64) {
+    let secs = duration.as_secs();
+
+    (
+        secs / SECONDS_PER_HOUR,
+        (secs % SECONDS_PER_HOUR) / SECONDS_PER_MINUTE,
+        secs % SECONDS_PER_MINUTE,
+    )
+}
+
+fn parse_colon_separated(input: &str) -> Result<u64> {
+    let fields = input
+        .split(':')
+        .map(|field| {
+            if field.is_empty() || !field.chars().all(|c| c.is_ascii_digit()) {
+                bail!("Invalid field \"{field}\" in duration \"{input}\"");
+            }
+
+            Ok(field.parse::<u64>()?)
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    match fields.as_slice() {
+        [minutes, seconds] if *seconds < 60 => {
+            total_seconds(input, &[(*minutes, SECONDS_PER_MINUTE), (*seconds, 1)])
+        }
+        [hours, minutes, seconds] if *minutes < 60 && *seconds < 60 => total_seconds(
+            input,
+            &[
+                (*hours, SECONDS_PER_HOUR),
+                (*minutes, SECONDS_PER_MINUTE),
+                (*seconds, 1),
+            ],
+        ),
+        _ => bail!("Invalid duration \"{input}\" (expected MM:SS or HH:MM:SS)"),
+    }
+}
+
+fn parse_unit_suffixed(input: &str) -> Result<u64> {
+    let mut parts = Vec::new();
+    let mut value = String::new();
+    let mut used_units = String::new();
+
+    for c in input.chars().filter(|c| !c.is_whitespace()) {
+        if c.is_ascii_digit() {
+            value.push(c);
+            continue;
+        }
+
+        let unit = c.to_ascii_lowercase();
+        let scale = match unit {
+            'h' => SECONDS_PER_HOUR,
+            'm' => SECONDS_PER_MINUTE,
+            's' => 1,
+            _ => bail!("Invalid unit '{c}' in duration \"{input}\" (expected h, m or s)"),
+        };
+
+        if value.is_empty() {
+            bail!("Missing value before '{c}' in duration \"{input}\"");
+        }
+        if used_units.contains(unit) {
+            bail!("Unit '{c}' is specified more than once in duration \"{input}\"");
+        }
+
+        parts.push((value.parse::<u64>()?, scale));
+        used_units.push(unit);
+        value.clear();
+    }
+
+    if !value.is_empty() {
+        bail!("Missing unit after \"{value}\" in duration \"{input}\"");
+    }
+
+    total_seconds(input, &parts)
+}
+
+/// Sum of values multiplied by their scale in seconds, failing instead of overflowing
+fn total_seconds(input: &str, parts: &[(u64, u64)]) -> Result<u64> {
+    parts
+        .iter()
+        .try_fold(0u64, |secs, (value, scale)| {
+            value
+                .checked_mul(*scale)
+                .and_then(|value| secs.checked_add(value))
+        })
+        .ok_or_else(|| anyhow!("Duration \"{input}\" is too long"))
+}
+
+/// Serde helper for durations written in human-friendly format
+pub mod serde_required {
+    use super::{format_duration, parse_duration};
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+    use std::time::Duration;
+
+    pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format_duration(duration))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        parse_duration(&String::deserialize(deserializer)?).map_err(D::Error::custom)
+    }
+}
+
+/// Serde helper for optional durations written in human-friendly format
+pub mod serde_option {
+    use super::{format_duration, parse_duration};
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+    use std::time::Duration;
+
+    pub fn serialize<S: Serializer>(
+        duration: &Option<Duration>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match duration {
+            Some(duration) => serializer.serialize_str(&format_duration(duration)),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Duration>, D::Error> {
+        Option::<String>::deserialize(deserializer)?
+            .map(|duration| parse_duration(&duration).map_err(D::Error::custom))
+            .transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_valid_durations() {
+        let cases = [
+            ("45s", 45),
+            ("1h30m", 5400),
+            ("1h 5m 30s", 3930),
+            ("2M", 120),
+            ("90:00", 5400),
+            ("1:30:00", 5400),
+            ("0:05", 5),
+            ("300", 300),
+            (" 10m ", 600),
+        ];
+
+        for (input, secs) in cases {
+            assert_eq!(
+                parse_duration(input).unwrap(),
+                Duration::from_secs(secs),
+                "{input}"
+            );
+        }
+    }
+
+    #[test]
+    fn parse_invalid_durations() {
+        let cases = [
+            "", "1x", "h", "1h1h", "1:60", "1:00:60", "1::00", "1:2:3:4", "5m3",
+        ];
+
+        for input in cases {
+            assert!(parse_duration(input).is_err(), "{input}");
+        }
+    }
+
+    #[test]
+    fn parse_overflowing_durations() {
+        let cases = [
+            "10000000000000000h",
+            "18446744073709551615h",
+            "18446744073709551615s 1m",
+            "5124095576030432:00:00",
+            "307445734561825861:00",
+            "99999999999999999999",
+        ];
+
+        for input in cases {
+            assert!(parse_duration(input).is_err(), "{input}");
+        }
+    }
+
+    #[test]
+    fn format_round_trip() {
+        let cases = [
+            (0, "0s"),
+            (45, "45s"),
+            (60, "1m"),
+            (3930, "1h5m30s"),
+            (7200, "2h"),
+        ];
+
+        for (secs, formatted) in cases {
+            let duration = Duration::from_secs(secs);
+
+            assert_eq!(format_duration(&duration), formatted);
+            assert_eq!(parse_duration(formatted).unwrap(), duration);
+        }
+    }
+}
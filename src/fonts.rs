@@ -0,0 +1,185 @@
+// Copyright 2025 Kensuke Saito
+// SPDX-License-Identifier: MIT
+
+use anyhow::Result;
+use needle_core::NeedleConfig;
+use std::{
+    ffi::OsString,
+    fs,
+    path::{Path, PathBuf},
+};
+
+const FONTS_DIR: &str = "fonts";
+const FONT_EXTENSIONS: [&str; 3] = ["ttf", "otf", "ttc"];
+#[cfg(all(unix, not(target_os = "macos")))]
+const FONTCONFIG_FILE: &str = "FONTCONFIG_FILE";
+
+/// Font files registered by `register_fonts`.
+/// Files generated for registration are removed when dropped,
+/// so this must be kept until fonts are no longer queried or loaded.
+#[derive(Debug, Default)]
+pub struct RegisteredFonts {
+    pub files: Vec<PathBuf>,
+    fontconfig: Option<FontconfigFile>,
+}
+
+/// Generated fontconfig config and the `FONTCONFIG_FILE` it has replaced
+#[derive(Debug)]
+struct FontconfigFile {
+    path: PathBuf,
+    previous: Option<OsString>,
+}
+
+impl Drop for RegisteredFonts {
+    fn drop(&mut self) {
+        let Some(fontconfig) = self.fontconfig.take() else {
+            return;
+        };
+
+        if let Err(e) = fs::remove_file(&fontconfig.path) {
+            log::error!("{}: {e}", fontconfig.path.display());
+        }
+        #[cfg(all(unix, not(target_os = "macos")))]
+        match fontconfig.previous {
+            Some(previous) => std::env::set_var(FONTCONFIG_FILE, previous),
+            None => std::env::remove_var(FONTCONFIG_FILE),
+        }
+    }
+}
+
+/// Make font files in `fonts/` of the config directory and `files` available to this process,
+/// so they are listed and loaded like installed system fonts.
+/// Must be called before fonts are queried for the first time.
+pub fn register_fonts(files: &[String]) -> Result<RegisteredFonts> {
+    let mut fonts = Vec::new();
+    let fonts_dir = NeedleConfig::config_path(false, Some(FONTS_DIR))?;
+
+    if fonts_dir.is_dir() {
+        for entry in fs::read_dir(&fonts_dir)? {
+            let path = entry?.path();
+
+            if is_font_file(&path) {
+                fonts.push(path);
+            }
+        }
+    }
+    for file in files {
+        let path = font_path(file)?;
+
+        if is_font_file(&path) && path.is_file() {
+            fonts.push(path);
+        } else {
+            log::error!("Font file \"{}\" does not exist", path.display());
+        }
+    }
+    fonts.sort();
+    fonts.dedup();
+
+    let fontconfig = match fonts.is_empty() {
+        true => None,
+        false => register(&fonts)?,
+    };
+
+    Ok(RegisteredFonts {
+        files: fonts,
+        fontconfig,
+    })
+}
+
+/// Path of font file, resolving relative paths from the config directory
+pub fn font_path(file: &str) -> Result<PathBuf> {
+    Ok(match PathBuf::from(file) {
+        path if path.is_absolute() => path,
+        _ => NeedleConfig::config_path(false, Some(file))?,
+    })
+}
+
+fn is_font_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| {
+            FONT_EXTENSIONS
+                .iter()
+                .any(|font| extension.eq_ignore_ascii_case(font))
+        })
+}
+
+/// Fonts are found through fontconfig, which reads `FONTCONFIG_FILE` on initialization.
+/// The generated config includes the current config and adds directories of the font files.
+#[cfg(all(unix, not(target_os = "macos")))]
+fn register(fonts: &[PathBuf]) -> Result<Option<FontconfigFile>> {
+    const SYSTEM_FONTCONFIG: &str = "/etc/fonts/fonts.conf";
+
+    let escape = |path: &Path| {
+        path.display()
+            .to_string()
+            .replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+    };
+    let previous = std::env::var_os(FONTCONFIG_FILE);
+    let base = previous
+        .clone()
+        .map(PathBuf::from)
+        .unwrap_or(PathBuf::from(SYSTEM_FONTCONFIG));
+    let mut dirs = fonts
+        .iter()
+        .filter_map(|font| font.parent())
+        .collect::<Vec<_>>();
+
+    dirs.sort();
+    dirs.dedup();
+
+    let dirs = dirs
+        .iter()
+        .map(|dir| format!("  <dir>{}</dir>\n", escape(dir)))
+        .collect::<String>();
+    let content = format!(
+        "<?xml version=\"1.0\"?>\n\
+         <!DOCTYPE fontconfig SYSTEM \"fonts.dtd\">\n\
+         <fontconfig>\n  \
+         <include ignore_missing=\"yes\">{}</include>\n\
+         {dirs}\
+         </fontconfig>\n",
+        escape(&base)
+    );
+    let path = std::env::temp_dir().join(format!("needle-{}-fonts.conf", std::process::id()));
+
+    fs::write(&path, content)?;
+    std::env::set_var(FONTCONFIG_FILE, &path);
+
+    Ok(Some(FontconfigFile { path, previous }))
+}
+
+/// Fonts are added as private fonts of this process (released on exit)
+#[cfg(windows)]
+fn register(fonts: &[PathBuf]) -> Result<Option<FontconfigFile>> {
+    use std::{iter, os::windows::ffi::OsStrExt, ptr};
+    use winapi::um::wingdi::{AddFontResourceExW, FR_PRIVATE};
+
+    for font in fonts {
+        let path = font
+            .as_os_str()
+            .encode_wide()
+            .chain(iter::once(0))
+            .collect::<Vec<_>>();
+
+        if unsafe { AddFontResourceExW(path.as_ptr(), FR_PRIVATE, ptr::null_mut()) } == 0 {
+            log::error!("Failed to load font file \"{}\"", font.display());
+        }
+    }
+
+    Ok(None)
+}
+
+#[cfg(not(any(windows, all(unix, not(target_os = "macos")))))]
+fn register(fonts: &[PathBuf]) -> Result<Option<FontconfigFile>> {
+    fonts.iter().for_each(|font| {
+        log::error!(
+            "Loading font files is not supported on this platform: \"{}\"",
+            font.display()
+        );
+    });
+
+    Ok(None)
+}
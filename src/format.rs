@@ -0,0 +1,229 @@
+// Copyright 2025 Kensuke Saito
+// SPDX-License-Identifier: MIT
+
+use anyhow::{bail, Result};
+use chrono::format::{Item, StrftimeItems};
+use std::time::Duration;
+
+/// Tokens available for timer format.
+///  - `{d}`: days
+///  - `{h}`, `{hh}`: hours (`{hh}` is zero padded)
+///  - `{m}`, `{mm}`: minutes (`{mm}` is zero padded)
+///  - `{s}`, `{ss}`: seconds (`{ss}` is zero padded)
+///  - `{f}`: tenths of a second
+///  - `{ff}`: hundredths of a second (zero padded)
+///  - `{ms}`: milliseconds
+///  - `{{`, `}}`: literal braces
+///
+/// The largest unit in the format is not wrapped (e.g. `{s}` displays total seconds).
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum TimerToken {
+    MilliSeconds,
+    Tenths,
+    Hundredths,
+    Seconds { padded: bool },
+    Minutes { padded: bool },
+    Hours { padded: bool },
+    Days,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+enum TimerItem {
+    Literal(String),
+    Token(TimerToken),
+}
+
+impl TimerToken {
+    fn parse(token: &str) -> Result<Self> {
+        match token {
+            "d" => Ok(Self::Days),
+            "h" => Ok(Self::Hours { padded: false }),
+            "hh" => Ok(Self::Hours { padded: true }),
+            "m" => Ok(Self::Minutes { padded: false }),
+            "mm" => Ok(Self::Minutes { padded: true }),
+            "s" => Ok(Self::Seconds { padded: false }),
+            "ss" => Ok(Self::Seconds { padded: true }),
+            "f" => Ok(Self::Tenths),
+            "ff" => Ok(Self::Hundredths),
+            "ms" => Ok(Self::MilliSeconds),
+            _ => bail!("Unknown token \"{{{token}}}\""),
+        }
+    }
+
+    /// Unit of token in milliseconds
+    const fn unit(&self) -> u128 {
+        match self {
+            Self::MilliSeconds => 1,
+            Self::Hundredths => 10,
+            Self::Tenths => 100,
+            Self::Seconds { .. } => 1_000,
+            Self::Minutes { .. } => 60 * 1_000,
+            Self::Hours { .. } => 60 * 60 * 1_000,
+            Self::Days => 24 * 60 * 60 * 1_000,
+        }
+    }
+
+    /// Unit of the next larger token in milliseconds
+    const fn wrap(&self) -> u128 {
+        match self {
+            Self::MilliSeconds | Self::Tenths | Self::Hundredths => {
+                Self::Seconds { padded: false }.unit()
+            }
+            Self::Seconds { .. } => Self::Minutes { padded: false }.unit(),
+            Self::Minutes { .. } => Self::Hours { padded: false }.unit(),
+            Self::Hours { .. } => Self::Days.unit(),
+            Self::Days => u128::MAX,
+        }
+    }
+
+    const fn rank(&self) -> u8 {
+        match self {
+            Self::MilliSeconds | Self::Tenths | Self::Hundredths => 0,
+            Self::Seconds { .. } => 1,
+            Self::Minutes { .. } => 2,
+            Self::Hours { .. } => 3,
+            Self::Days => 4,
+        }
+    }
+
+    fn format(&self, millis: u128, largest: bool) -> String {
+        let value = if largest {
+            millis / self.unit()
+        } else {
+            (millis % self.wrap()) / self.unit()
+        };
+
+        match self {
+            Self::MilliSeconds => format!("{value:03}"),
+            Self::Hundredths => format!("{value:02}"),
+            Self::Seconds { padded: true }
+            | Self::Minutes { padded: true }
+            | Self::Hours { padded: true } => format!("{value:02}"),
+            _ => format!("{value}"),
+        }
+    }
+}
+
+fn parse_timer_format(format: &str) -> Result<Vec<TimerItem>> {
+    let mut items = Vec::new();
+    let mut literal = String::new();
+    let mut chars = format.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                literal.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                literal.push('}');
+            }
+            '{' => {
+                let mut token = String::new();
+
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => token.push(c),
+                        None => bail!("Unclosed token \"{{{token}\""),
+                    }
+                }
+                if !literal.is_empty() {
+                    items.push(TimerItem::Literal(literal.clone()));
+                    literal.clear();
+                }
+                items.push(TimerItem::Token(TimerToken::parse(&token)?));
+            }
+            '}' => bail!("Unmatched '}}' (use \"}}}}\" for literal brace)"),
+            _ => literal.push(c),
+        }
+    }
+    if !literal.is_empty() {
+        items.push(TimerItem::Literal(literal));
+    }
+
+    Ok(items)
+}
+
+/// Validate chrono strftime pattern used for clock mode
+pub fn validate_clock_format(format: &str) -> Result<()> {
+    if StrftimeItems::new(format).any(|item| matches!(item, Item::Error)) {
+        bail!("Invalid strftime pattern \"{format}\"");
+    }
+
+    Ok(())
+}
+
+/// Format local time with chrono strftime pattern
+pub fn format_clock(format: &str) -> Result<String> {
+    validate_clock_format(format)?;
+
+    Ok(chrono::Local::now().format(format).to_string())
+}
+
+/// Format duration with timer token pattern
+pub fn format_timer(format: &str, duration: Duration) -> Result<String> {
+    let items = parse_timer_format(format)?;
+    let millis = duration.as_millis();
+    let largest = items
+        .iter()
+        .filter_map(|item| match item {
+            TimerItem::Token(token) => Some(token.rank()),
+            TimerItem::Literal(_) => None,
+        })
+        .max();
+
+    Ok(items
+        .iter()
+        .map(|item| match item {
+            TimerItem::Literal(literal) => literal.clone(),
+            TimerItem::Token(token) => token.format(millis, Some(token.rank()) == largest),
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_timer_tokens() {
+        let duration = Duration::from_millis(((26 * 60 + 5) * 60 + 7) * 1_000 + 89);
+        let cases = [
+            ("{hh}:{mm}:{ss}", "26:05:07"),
+            ("{d}d {hh}:{mm}:{ss}", "1d 02:05:07"),
+            ("{h}h {m}m", "26h 5m"),
+            ("{m}:{ss}", "1565:07"),
+            ("{s}", "93907"),
+            ("{ss}.{f}", "93907.0"),
+            ("{ss}.{ff}", "93907.08"),
+            ("{ss}.{ms}", "93907.089"),
+            ("{{{mm}}}", "{1565}"),
+            ("no tokens", "no tokens"),
+        ];
+
+        for (format, formatted) in cases {
+            assert_eq!(
+                format_timer(format, duration).unwrap(),
+                formatted,
+                "{format}"
+            );
+        }
+    }
+
+    #[test]
+    fn format_timer_rejects_invalid_formats() {
+        let cases = ["{x}", "{hh", "}", "{mm}}"];
+
+        for format in cases {
+            assert!(format_timer(format, Duration::ZERO).is_err(), "{format}");
+        }
+    }
+
+    #[test]
+    fn validate_strftime_patterns() {
+        assert!(validate_clock_format("%a %d %b %H:%M:%S").is_ok());
+        assert!(validate_clock_format("%Q").is_err());
+    }
+}
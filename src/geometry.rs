@@ -0,0 +1,110 @@
+// Copyright 2025 Kensuke Saito
+// SPDX-License-Identifier: MIT
+
+use anyhow::{bail, Context, Result};
+
+/// Window geometry given on command line
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Geometry {
+    /// Inner size in physical pixels
+    pub size: Option<[u32; 2]>,
+    /// Position of top-left corner of window in screen coordinates
+    pub position: Option<[i32; 2]>,
+}
+
+/// Parse X11 style geometry (`WIDTHxHEIGHT{+-}X{+-}Y`).
+/// Either size or position can be omitted (e.g. `640x200`, `+100+100`).
+/// Signs of X and Y are the signs of the coordinates.
+pub fn parse_geometry(input: &str) -> Result<Geometry> {
+    let input = input.trim();
+
+    if input.is_empty() {
+        bail!("Geometry is empty");
+    }
+
+    let (size, position) = match input.find(['+', '-']) {
+        Some(i) => input.split_at(i),
+        None => (input, ""),
+    };
+    let size = if size.is_empty() {
+        None
+    } else {
+        let (width, height) = size
+            .split_once(['x', 'X'])
+            .with_context(|| format!("Invalid size \"{size}\" (expected WIDTHxHEIGHT)"))?;
+        let size = [width.parse::<u32>()?, height.parse::<u32>()?];
+
+        if size.contains(&0) {
+            bail!("Window size must be greater than 0");
+        }
+
+        Some(size)
+    };
+    let position = if position.is_empty() {
+        None
+    } else {
+        let y = position[1..]
+            .find(['+', '-'])
+            .map(|i| i + 1)
+            .with_context(|| format!("Invalid position \"{position}\" (expected +X+Y)"))?;
+        let (x, y) = position.split_at(y);
+
+        Some([parse_coordinate(x)?, parse_coordinate(y)?])
+    };
+
+    Ok(Geometry { size, position })
+}
+
+fn parse_coordinate(coordinate: &str) -> Result<i32> {
+    let value = coordinate.strip_prefix('+').unwrap_or(coordinate);
+
+    value
+        .parse()
+        .with_context(|| format!("Invalid coordinate \"{coordinate}\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_valid_geometries() {
+        let cases = [
+            ("640x200+100+100", Some([640, 200]), Some([100, 100])),
+            ("640X200", Some([640, 200]), None),
+            ("+100+100", None, Some([100, 100])),
+            ("-10+20", None, Some([-10, 20])),
+            ("800x600-0-50", Some([800, 600]), Some([0, -50])),
+            (" 320x240 ", Some([320, 240]), None),
+        ];
+
+        for (input, size, position) in cases {
+            assert_eq!(
+                parse_geometry(input).unwrap(),
+                Geometry { size, position },
+                "{input}"
+            );
+        }
+    }
+
+    #[test]
+    fn parse_invalid_geometries() {
+        let cases = [
+            "",
+            "640",
+            "640x",
+            "x200",
+            "0x200",
+            "640x0",
+            "640x200+100",
+            "+100",
+            "+x+y",
+            "640x200+1+2+3",
+            "-640x200",
+        ];
+
+        for input in cases {
+            assert!(parse_geometry(input).is_err(), "{input}");
+        }
+    }
+}
@@ -0,0 +1,342 @@
+// Copyright 2025 Kensuke Saito
+// SPDX-License-Identifier: MIT
+
+use serde::{Deserialize, Serialize};
+use std::{
+    borrow::Cow,
+    fmt::{self, Display, Formatter},
+    sync::atomic::{AtomicI8, Ordering},
+};
+
+/// Language of UI and CLI messages
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Language {
+    /// Detect from system locale
+    #[default]
+    Auto,
+    English,
+    Japanese,
+}
+
+/// Language in use (always resolved, never `Auto`)
+static LANGUAGE: AtomicI8 = AtomicI8::new(Language::ENGLISH);
+
+/// Japanese translations keyed by English text (without `##` ID suffix)
+const JAPANESE: &[(&str, &str)] = &[
+    // Menu
+    ("Needle Settings", "Needle 設定"),
+    ("Settings", "設定"),
+    ("Save", "保存"),
+    ("Profiles", "プロファイル"),
+    ("* Unsaved changes", "* 未保存の変更があります"),
+    ("Revert to saved", "保存済みの設定に戻す"),
+    ("Reset to defaults", "デフォルトに戻す"),
+    ("Language", "言語"),
+    ("Window", "ウィンドウ"),
+    ("Always on top", "常に最前面に表示"),
+    ("Show title bar", "タイトルバーを表示"),
+    ("Hide from taskbar", "タスクバーに表示しない"),
+    (
+        "Click-through (while menu is closed)",
+        "クリックを透過 (メニューを閉じている間)",
+    ),
+    (
+        "Press \"INSERT\" on the focused window or run \"needle --send menu\" to open the menu again",
+        "ウィンドウにフォーカスがある状態で\"INSERT\"キーを押すか\"needle --send menu\"を実行するとメニューを再表示",
+    ),
+    (
+        "Hiding from taskbar applies on next launch",
+        "タスクバーに表示しない設定は次回起動時に反映",
+    ),
+    (
+        "Remember window size and position",
+        "ウィンドウのサイズと位置を記憶",
+    ),
+    ("Auto", "自動"),
+    ("English", "English"),
+    ("Japanese", "日本語"),
+    (
+        "Press \"INSERT\" to toggle menu.",
+        "\"INSERT\"キーでメニューを表示/非表示",
+    ),
+    ("Save config:", "設定の保存:"),
+    ("Repository:", "リポジトリ:"),
+    ("License:", "ライセンス:"),
+    // Quit confirmation
+    ("Quit Needle", "Needle を終了"),
+    ("There are unsaved changes.", "未保存の変更があります。"),
+    ("Save and Quit", "保存して終了"),
+    ("Quit without Saving", "保存せずに終了"),
+    ("Cancel", "キャンセル"),
+    // Shader
+    ("Shader Error", "シェーダエラー"),
+    (
+        "Shader failed to load. Fix the shader and save it to reload.",
+        "シェーダの読み込みに失敗しました。修正して保存すると再読み込みされます。",
+    ),
+    // Background
+    ("Color", "色"),
+    ("Hex", "16進数"),
+    ("Style", "スタイル"),
+    ("Solid", "単色"),
+    ("Linear Gradient", "線形グラデーション"),
+    ("Radial Gradient", "放射グラデーション"),
+    ("Panel", "パネル"),
+    ("Angle", "角度"),
+    ("Stop", "色"),
+    ("Position", "位置"),
+    ("Add Stop", "色を追加"),
+    ("Remove Stop", "色を削除"),
+    ("Width (%)", "幅 (%)"),
+    ("Height (%)", "高さ (%)"),
+    ("Corner Radius", "角の半径"),
+    ("Border Width", "枠線の幅"),
+    ("Border Color", "枠線の色"),
+    ("Custom Shader", "カスタムシェーダ"),
+    ("Use custom shader", "カスタムシェーダを使用"),
+    ("WGSL File", "WGSLファイル"),
+    ("Load", "読み込み"),
+    (
+        "Relative paths are resolved from the config directory",
+        "相対パスは設定ディレクトリからのパスとして扱われます",
+    ),
+    // Clock/Timer
+    ("Font", "フォント"),
+    ("List all system fonts", "すべてのシステムフォントを表示"),
+    (
+        "Font files in \"fonts\" of the config directory are listed too",
+        "設定ディレクトリの\"fonts\"内のフォントファイルも表示されます",
+    ),
+    ("Font Color", "テキストの色"),
+    (
+        "Alpha blends the text toward the background color (text is not made translucent)",
+        "アルファ値はテキストの色を背景色に近づけます (テキストは半透明になりません)",
+    ),
+    ("Font Scale", "テキストのサイズ"),
+    ("Clock Position", "時刻の配置"),
+    ("Center", "中央"),
+    ("Top", "上"),
+    ("Bottom", "下"),
+    ("Left", "左"),
+    ("Right", "右"),
+    ("Top Left", "左上"),
+    ("Top Right", "右上"),
+    ("Bottom Left", "左下"),
+    ("Bottom Right", "右下"),
+    ("Offset", "オフセット"),
+    ("Offset Unit", "オフセットの単位"),
+    ("Reset Offset", "オフセットをリセット"),
+    ("Pixel", "ピクセル"),
+    ("Percent", "パーセント"),
+    (
+        "Drag text with mouse to move it while this menu is open",
+        "メニューを表示中はマウスでテキストをドラッグして移動できます",
+    ),
+    ("Outline/Shadow", "縁取り/影"),
+    ("Outline", "縁取り"),
+    ("Outline Width", "縁取りの幅"),
+    ("Outline Color", "縁取りの色"),
+    ("Drop Shadow", "影"),
+    ("Shadow Offset", "影のオフセット"),
+    ("Shadow Color", "影の色"),
+    ("Mode:", "モード:"),
+    ("Format Mode", "表示形式"),
+    ("Custom Format", "カスタムフォーマット"),
+    ("Clock Format", "時刻のフォーマット"),
+    ("Timer Format", "タイマーのフォーマット"),
+    ("Preview:", "プレビュー:"),
+    (
+        "strftime pattern (e.g. \"%a %d %b %H:%M:%S\")",
+        "strftime形式 (例: \"%a %d %b %H:%M:%S\")",
+    ),
+    (
+        "Tokens: {d} {h} {hh} {m} {mm} {s} {ss} {f} {ff} {ms}",
+        "トークン: {d} {h} {hh} {m} {mm} {s} {ss} {f} {ff} {ms}",
+    ),
+    ("Clock Mode", "時計モード"),
+    ("Timer Precision", "タイマーの精度"),
+    ("Tenths", "1/10秒"),
+    ("Hundredths", "1/100秒"),
+    ("Milliseconds", "1/1000秒"),
+    (
+        "Press \"SPACE\" to start/stop timer",
+        "\"SPACE\"キーでタイマーを開始/停止",
+    ),
+    ("Countdown Duration", "カウントダウンの時間"),
+    ("Duration", "時間"),
+    ("Hours", "時"),
+    ("Minutes", "分"),
+    ("Seconds", "秒"),
+    ("On Finish", "終了時"),
+    ("Message", "メッセージ"),
+    ("Shown in place of the time", "時刻の代わりに表示"),
+    ("Blink", "点滅"),
+    ("Show for limited time", "表示する時間を指定"),
+    ("Start Next Timer", "次のタイマーを開始"),
+    ("None", "なし"),
+    ("Repeat", "繰り返し"),
+    ("Command", "コマンド"),
+    (
+        "Timer is described in NEEDLE_TIMER_* environment variables",
+        "タイマーの情報は環境変数 NEEDLE_TIMER_* で渡されます",
+    ),
+    ("Marker File", "マーカーファイル"),
+    ("Subtitle", "サブタイトル"),
+    ("Pomodoro", "ポモドーロ"),
+    ("Pomodoro mode", "ポモドーロモード"),
+    (
+        "Press \"S\" to skip to next phase",
+        "\"S\"キーで次のフェーズにスキップ",
+    ),
+    ("Rounds before long break", "長い休憩までのラウンド数"),
+    (
+        "Start next phase automatically",
+        "次のフェーズを自動的に開始",
+    ),
+    ("Work", "作業"),
+    ("Short Break", "短い休憩"),
+    ("Long Break", "長い休憩"),
+    ("Duration (minutes)", "時間 (分)"),
+    ("Custom text color", "テキストの色を指定"),
+    ("Text Color", "テキストの色"),
+    ("Custom background color", "背景色を指定"),
+    ("Background Color", "背景色"),
+    ("Playlist", "プレイリスト"),
+    ("Run playlist", "プレイリストを実行"),
+    (
+        "Press \"S\" to skip to next segment",
+        "\"S\"キーで次のセグメントにスキップ",
+    ),
+    (
+        "Start next segment automatically",
+        "次のセグメントを自動的に開始",
+    ),
+    ("Segment", "セグメント"),
+    ("Mode", "モード"),
+    ("Countdown", "カウントダウン"),
+    ("Count Up", "カウントアップ"),
+    ("End at duration", "指定時間で終了"),
+    ("Move Up", "上に移動"),
+    ("Add Segment", "セグメントを追加"),
+    ("Remove Segment", "セグメントを削除"),
+    ("Show subtitle", "サブタイトルを表示"),
+    ("Content", "内容"),
+    ("Date", "日付"),
+    ("Label", "ラベル"),
+    ("Date and Label", "日付とラベル"),
+    ("Date Format", "日付のフォーマット"),
+    ("Placement", "配置"),
+    ("Below", "下"),
+    ("Above", "上"),
+    ("Spacing", "間隔"),
+    // FPS
+    ("Toggle FPS visualization", "FPSの表示"),
+    ("Enable", "有効"),
+    ("Disable", "無効"),
+    ("FPS Position", "FPSの配置"),
+    // Profiles
+    ("Profile", "プロファイル"),
+    (
+        "No profiles saved yet",
+        "保存されたプロファイルはありません",
+    ),
+    ("Name", "名前"),
+    ("Save Profile", "プロファイルを保存"),
+    ("Save As", "名前を付けて保存"),
+    ("Duplicate As", "複製"),
+    ("Delete Profile", "プロファイルを削除"),
+    (
+        "Press \"F2\" to switch to next profile",
+        "\"F2\"キーで次のプロファイルに切り替え",
+    ),
+];
+
+impl Language {
+    pub const AUTO: i8 = 0;
+    pub const ENGLISH: i8 = 1;
+    pub const JAPANESE: i8 = 2;
+    pub const MAX: i8 = 2;
+    /// Highest language selectable in the menu.
+    /// Japanese is left out until the menu font has Japanese glyphs.
+    pub const MENU_MAX: i8 = 1;
+
+    /// Language to use, detecting `Auto` from system locale.
+    /// The menu falls back to English, as the menu font has no Japanese glyphs
+    /// (Japanese would be drawn as missing glyphs).
+    pub fn resolve(self, menu: bool) -> Self {
+        match self {
+            _ if menu => Self::English,
+            Self::Auto => match sys_locale::get_locale() {
+                Some(locale) if locale.to_lowercase().starts_with("ja") => Self::Japanese,
+                _ => Self::English,
+            },
+            language => language,
+        }
+    }
+}
+
+impl From<i8> for Language {
+    fn from(value: i8) -> Self {
+        match value {
+            1 => Self::English,
+            2 => Self::Japanese,
+            _ => Self::Auto,
+        }
+    }
+}
+
+impl From<Language> for i8 {
+    fn from(value: Language) -> Self {
+        match value {
+            Language::Auto => Language::AUTO,
+            Language::English => Language::ENGLISH,
+            Language::Japanese => Language::JAPANESE,
+        }
+    }
+}
+
+impl Display for Language {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            Self::Auto => "Auto",
+            Self::English => "English",
+            Self::Japanese => "Japanese",
+        };
+
+        write!(f, "{msg}")
+    }
+}
+
+/// Set language of UI
+pub fn set_language(language: Language) {
+    LANGUAGE.store(language.resolve(true).into(), Ordering::Relaxed);
+}
+
+/// Set language of CLI messages
+pub fn set_cli_language(language: Language) {
+    LANGUAGE.store(language.resolve(false).into(), Ordering::Relaxed);
+}
+
+pub fn language() -> Language {
+    LANGUAGE.load(Ordering::Relaxed).into()
+}
+
+/// Translate `text` to the language in use.
+/// ImGui ID suffixes (`Label##id`) are kept as is, and untranslated text is returned unchanged.
+pub fn tr(text: &str) -> Cow<'_, str> {
+    let catalog = match language() {
+        Language::Japanese => JAPANESE,
+        _ => return Cow::Borrowed(text),
+    };
+    let (label, id) = match text.find("##") {
+        Some(i) => text.split_at(i),
+        None => (text, ""),
+    };
+
+    match catalog.iter().find(|(english, _)| *english == label) {
+        Some((_, translated)) if id.is_empty() => Cow::Borrowed(translated),
+        Some((_, translated)) => Cow::Owned(format!("{translated}{id}")),
+        None => Cow::Borrowed(text),
+    }
+}
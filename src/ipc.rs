@@ -0,0 +1,511 @@
+// Copyright 2025 Kensuke Saito
+// SPDX-License-Identifier: MIT
+
+use crate::duration::{format_duration, parse_duration};
+use anyhow::{anyhow, bail, Result};
+use std::{
+    env,
+    fmt::{self, Display, Formatter},
+    io::{self, BufRead, BufReader, Read, Write},
+    str::FromStr,
+    sync::{mpsc, Arc, Mutex},
+    thread,
+    time::Duration,
+};
+use winit::event_loop::EventLoopProxy;
+
+const REPLY_OK: &str = "ok";
+const REPLY_ERROR: &str = "error: ";
+/// Time to wait for a command from a connected client
+#[cfg(unix)]
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Command sent to the running instance with `--send`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Command {
+    /// Start timer
+    Start,
+    /// Stop (pause) timer
+    Stop,
+    /// Start/Stop timer
+    Toggle,
+    /// Reset timer of current mode
+    Reset,
+    /// Switch to clock
+    Clock,
+    /// Switch to count up timer
+    CountUp,
+    /// Switch to countdown timer (duration defaults to the one in config)
+    Countdown(Option<Duration>),
+    /// Skip to next phase of Pomodoro mode or next segment of playlist
+    Skip,
+    /// Receive events until the running instance exits
+    Events,
+    /// Open settings menu (makes click-through window clickable again)
+    Menu,
+    Quit,
+}
+
+/// Command forwarded to the event loop, with the client waiting for its result
+#[derive(Debug)]
+pub struct Request {
+    pub command: Command,
+    reply: mpsc::Sender<Result<(), String>>,
+}
+
+/// Event sent to clients subscribed with `events`
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event {
+    /// Alarm has fired (with its label)
+    Alarm(String),
+}
+
+/// Clients subscribed to events
+#[derive(Clone, Default)]
+pub struct Events {
+    clients: Arc<Mutex<Vec<Box<dyn Write + Send>>>>,
+}
+
+/// Keeps the control socket of this instance open.
+/// Commands received are forwarded to the event loop.
+pub struct Server {
+    #[cfg(unix)]
+    path: std::path::PathBuf,
+    events: Events,
+}
+
+impl Command {
+    /// Parse command given as separate words (e.g. `mode countdown 5m`)
+    pub fn parse<S: AsRef<str>>(words: &[S]) -> Result<Self> {
+        let words = words.iter().map(|word| word.as_ref()).collect::<Vec<_>>();
+
+        Ok(match words[..] {
+            ["start"] => Self::Start,
+            ["stop"] => Self::Stop,
+            ["toggle"] => Self::Toggle,
+            ["reset"] => Self::Reset,
+            ["skip"] => Self::Skip,
+            ["events"] => Self::Events,
+            ["menu"] => Self::Menu,
+            ["quit"] => Self::Quit,
+            ["mode", "clock"] => Self::Clock,
+            ["mode", "countup"] => Self::CountUp,
+            ["mode", "countdown"] => Self::Countdown(None),
+            ["mode", "countdown", duration] => Self::Countdown(Some(parse_duration(duration)?)),
+            [] => bail!("Command is empty"),
+            _ => bail!(
+                "Unknown command \"{}\" (expected start, stop, toggle, reset, skip, events, menu, quit or mode clock|countup|countdown [DURATION])",
+                words.join(" ")
+            ),
+        })
+    }
+}
+
+impl FromStr for Command {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse(&s.split_whitespace().collect::<Vec<_>>())
+    }
+}
+
+impl Display for Command {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            Self::Start => "start".to_string(),
+            Self::Stop => "stop".to_string(),
+            Self::Toggle => "toggle".to_string(),
+            Self::Reset => "reset".to_string(),
+            Self::Skip => "skip".to_string(),
+            Self::Events => "events".to_string(),
+            Self::Menu => "menu".to_string(),
+            Self::Clock => "mode clock".to_string(),
+            Self::CountUp => "mode countup".to_string(),
+            Self::Countdown(None) => "mode countdown".to_string(),
+            Self::Countdown(Some(duration)) => {
+                format!("mode countdown {}", format_duration(duration))
+            }
+            Self::Quit => "quit".to_string(),
+        };
+
+        write!(f, "{msg}")
+    }
+}
+
+impl Request {
+    /// Reply to the client with the result of the command
+    pub fn reply(self, result: Result<()>) {
+        // Client may have disconnected already
+        let _ = self.reply.send(result.map_err(|e| e.to_string()));
+    }
+}
+
+impl Display for Event {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Alarm(label) => write!(f, "alarm {label}"),
+        }
+    }
+}
+
+impl Events {
+    /// Send event to subscribed clients, dropping those which have disconnected
+    pub fn emit(&self, event: &Event) {
+        match self.clients.lock() {
+            Ok(mut clients) => clients.retain_mut(|client| {
+                writeln!(client, "{event}")
+                    .and_then(|_| client.flush())
+                    .is_ok()
+            }),
+            Err(e) => log::error!("{e}"),
+        }
+    }
+
+    fn subscribe(&self, client: Box<dyn Write + Send>) {
+        match self.clients.lock() {
+            Ok(mut clients) => clients.push(client),
+            Err(e) => log::error!("{e}"),
+        }
+    }
+}
+
+impl Server {
+    /// Clients subscribed to events of this instance
+    pub fn events(&self) -> Events {
+        self.events.clone()
+    }
+}
+
+/// Send command to the running instance.
+/// Fails if no instance is running or the command has been rejected.
+/// Events received after `events` are printed until the running instance exits.
+pub fn send(command: &Command) -> Result<()> {
+    let mut stream =
+        connect().map_err(|e| anyhow!("Failed to connect to running instance of needle ({e})"))?;
+    let mut reply = String::new();
+
+    writeln!(stream, "{command}")?;
+    stream.flush()?;
+
+    let mut reader = BufReader::new(stream);
+
+    reader.read_line(&mut reply)?;
+    match reply.trim() {
+        REPLY_OK => (),
+        reply => bail!("{}", reply.strip_prefix(REPLY_ERROR).unwrap_or(reply)),
+    }
+    if *command == Command::Events {
+        for event in reader.lines() {
+            println!("{}", event?);
+        }
+    }
+
+    Ok(())
+}
+
+/// Check if another instance is listening for commands
+pub fn is_running() -> bool {
+    connect().is_ok()
+}
+
+/// Read a single command from `stream`, forward it to the event loop and reply with the result.
+/// Connections without command (from `is_running`) are ignored.
+/// Returns true if the client has subscribed to events.
+fn handle<S: Read + Write>(stream: &mut S, proxy: &EventLoopProxy<Request>) -> Result<bool> {
+    let mut line = String::new();
+
+    BufReader::new(&mut *stream).read_line(&mut line)?;
+    if line.trim().is_empty() {
+        return Ok(false);
+    }
+
+    let result = line.parse::<Command>().and_then(|command| {
+        log::info!("Received command \"{command}\"");
+        match command {
+            Command::Events => Ok(true),
+            command => {
+                let (reply, result) = mpsc::channel();
+                let exiting = || anyhow!("needle is exiting");
+
+                proxy
+                    .send_event(Request { command, reply })
+                    .map_err(|_| exiting())?;
+                // Request is dropped without reply if the event loop exits
+                result
+                    .recv()
+                    .map_err(|_| exiting())?
+                    .map_err(|e| anyhow!(e))?;
+
+                Ok(false)
+            }
+        }
+    });
+
+    match &result {
+        Ok(_) => writeln!(stream, "{REPLY_OK}")?,
+        Err(e) => writeln!(stream, "{REPLY_ERROR}{e}")?,
+    }
+    stream.flush()?;
+
+    Ok(result.unwrap_or(false))
+}
+
+/// Socket in the runtime directory of the user
+#[cfg(unix)]
+fn socket_path() -> std::path::PathBuf {
+    env::var_os("XDG_RUNTIME_DIR")
+        .map(std::path::PathBuf::from)
+        .unwrap_or_else(env::temp_dir)
+        .join(format!(
+            "needle-{}.sock",
+            env::var("USER").unwrap_or_default()
+        ))
+}
+
+#[cfg(unix)]
+fn connect() -> io::Result<std::os::unix::net::UnixStream> {
+    std::os::unix::net::UnixStream::connect(socket_path())
+}
+
+/// Listen for commands from other instances.
+/// Socket left behind by an instance which has not exited cleanly is replaced,
+/// while the socket of an instance still listening is kept.
+/// Each connection is handled in its own thread, so a client which does not send a command
+/// does not block others.
+#[cfg(unix)]
+pub fn listen(proxy: EventLoopProxy<Request>) -> Result<Server> {
+    use std::os::unix::net::UnixListener;
+
+    let path = socket_path();
+
+    if path.exists() {
+        if connect().is_ok() {
+            bail!("Another instance is listening on {}", path.display());
+        }
+        std::fs::remove_file(&path)?;
+    }
+
+    let listener = UnixListener::bind(&path)?;
+    let events = Events::default();
+    let subscribers = events.clone();
+
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let stream = match stream {
+                Ok(stream) => stream,
+                Err(e) => {
+                    log::error!("{e}");
+                    continue;
+                }
+            };
+            let (proxy, subscribers) = (proxy.clone(), subscribers.clone());
+
+            thread::spawn(move || {
+                let mut stream = stream;
+                let result = stream
+                    .set_read_timeout(Some(READ_TIMEOUT))
+                    .map_err(anyhow::Error::from)
+                    .and_then(|_| handle(&mut stream, &proxy));
+
+                match result {
+                    Ok(true) => subscribers.subscribe(Box::new(stream)),
+                    Ok(false) => (),
+                    Err(e) => log::error!("{e}"),
+                }
+            });
+        }
+    });
+
+    Ok(Server { path, events })
+}
+
+#[cfg(unix)]
+impl Drop for Server {
+    fn drop(&mut self) {
+        if let Err(e) = std::fs::remove_file(&self.path) {
+            log::error!("{e}");
+        }
+    }
+}
+
+#[cfg(windows)]
+fn pipe_name() -> String {
+    format!(
+        r"\\.\pipe\needle-{}",
+        env::var("USERNAME").unwrap_or_default()
+    )
+}
+
+#[cfg(windows)]
+fn connect() -> io::Result<std::fs::File> {
+    std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(pipe_name())
+}
+
+/// Create instance of named pipe (`first` fails if the pipe already exists)
+#[cfg(windows)]
+fn create_pipe(name: &[u16], first: bool) -> io::Result<std::fs::File> {
+    use std::{os::windows::io::FromRawHandle, ptr};
+    use winapi::um::{
+        handleapi::INVALID_HANDLE_VALUE,
+        winbase::{
+            CreateNamedPipeW, FILE_FLAG_FIRST_PIPE_INSTANCE, PIPE_ACCESS_DUPLEX,
+            PIPE_READMODE_BYTE, PIPE_REJECT_REMOTE_CLIENTS, PIPE_TYPE_BYTE,
+            PIPE_UNLIMITED_INSTANCES, PIPE_WAIT,
+        },
+    };
+    const BUFFER_SIZE: u32 = 512;
+
+    let open_mode = if first {
+        PIPE_ACCESS_DUPLEX | FILE_FLAG_FIRST_PIPE_INSTANCE
+    } else {
+        PIPE_ACCESS_DUPLEX
+    };
+    let handle = unsafe {
+        CreateNamedPipeW(
+            name.as_ptr(),
+            open_mode,
+            PIPE_TYPE_BYTE | PIPE_READMODE_BYTE | PIPE_WAIT | PIPE_REJECT_REMOTE_CLIENTS,
+            PIPE_UNLIMITED_INSTANCES,
+            BUFFER_SIZE,
+            BUFFER_SIZE,
+            0,
+            ptr::null_mut(),
+        )
+    };
+
+    if handle == INVALID_HANDLE_VALUE {
+        return Err(io::Error::last_os_error());
+    }
+
+    Ok(unsafe { std::fs::File::from_raw_handle(handle as _) })
+}
+
+/// Listen for commands from other instances.
+/// Each connection is handled in its own thread, so a client which does not send a command
+/// does not block others.
+#[cfg(windows)]
+pub fn listen(proxy: EventLoopProxy<Request>) -> Result<Server> {
+    use std::{iter, os::windows::io::AsRawHandle, ptr};
+    use winapi::{shared::winerror::ERROR_PIPE_CONNECTED, um::namedpipeapi::ConnectNamedPipe};
+
+    let name = pipe_name()
+        .encode_utf16()
+        .chain(iter::once(0))
+        .collect::<Vec<_>>();
+    let mut pipe = Some(create_pipe(&name, true)?);
+    let events = Events::default();
+    let subscribers = events.clone();
+
+    thread::spawn(move || loop {
+        let mut pipe = match pipe
+            .take()
+            .map(Ok)
+            .unwrap_or_else(|| create_pipe(&name, false))
+        {
+            Ok(pipe) => pipe,
+            Err(e) => {
+                log::error!("{e}");
+
+                return;
+            }
+        };
+        let connected = unsafe { ConnectNamedPipe(pipe.as_raw_handle() as _, ptr::null_mut()) }
+            != 0
+            || io::Error::last_os_error().raw_os_error() == Some(ERROR_PIPE_CONNECTED as i32);
+
+        if !connected {
+            log::error!("{}", io::Error::last_os_error());
+            continue;
+        }
+
+        let (proxy, subscribers) = (proxy.clone(), subscribers.clone());
+
+        thread::spawn(move || {
+            match handle(&mut pipe, &proxy) {
+                Ok(true) => {
+                    subscribers.subscribe(Box::new(pipe));
+                    return;
+                }
+                Ok(false) => (),
+                Err(e) => log::error!("{e}"),
+            }
+            // Wait for the client to read the reply before the pipe is closed
+            if let Err(e) = pipe.sync_all() {
+                log::error!("{e}");
+            }
+        });
+    });
+
+    Ok(Server { events })
+}
+
+#[cfg(not(any(unix, windows)))]
+fn connect() -> io::Result<std::fs::File> {
+    Err(io::Error::from(io::ErrorKind::Unsupported))
+}
+
+#[cfg(not(any(unix, windows)))]
+pub fn listen(_proxy: EventLoopProxy<Request>) -> Result<Server> {
+    bail!("Single instance control is not supported on this platform")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_commands() {
+        let cases = [
+            ("start", Command::Start),
+            ("stop", Command::Stop),
+            ("toggle", Command::Toggle),
+            ("reset", Command::Reset),
+            ("skip", Command::Skip),
+            ("events", Command::Events),
+            ("menu", Command::Menu),
+            ("quit", Command::Quit),
+            ("mode clock", Command::Clock),
+            ("mode countup", Command::CountUp),
+            ("mode countdown", Command::Countdown(None)),
+            (
+                "mode countdown 5m",
+                Command::Countdown(Some(Duration::from_secs(300))),
+            ),
+            (
+                "  mode   countdown 1h30m ",
+                Command::Countdown(Some(Duration::from_secs(5400))),
+            ),
+        ];
+
+        for (input, command) in cases {
+            assert_eq!(input.parse::<Command>().unwrap(), command, "{input}");
+            assert_eq!(
+                command.to_string().parse::<Command>().unwrap(),
+                command,
+                "{input}"
+            );
+        }
+    }
+
+    #[test]
+    fn reject_invalid_commands() {
+        let cases = [
+            "",
+            "   ",
+            "begin",
+            "Start",
+            "start now",
+            "mode",
+            "mode timer",
+            "mode countdown 5x",
+            "mode countdown 5m 10s",
+        ];
+
+        for input in cases {
+            assert!(input.parse::<Command>().is_err(), "{input}");
+        }
+    }
+}
@@ -2,13 +2,30 @@
 // Copyright 2025 Kensuke Saito
 // SPDX-License-Identifier: MIT
 
+mod alarm;
 mod app;
+mod check;
+mod color;
+mod config;
+mod document;
+mod duration;
+mod fonts;
+mod format;
+mod geometry;
+mod i18n;
+mod ipc;
+mod migrate;
 mod needle;
 mod options;
+mod process;
+mod profile;
 
 use app::*;
+use check::*;
+use config::*;
 use needle::*;
 use options::*;
+use profile::*;
 
 use anyhow::Result;
 use clap::Parser;
@@ -30,10 +47,21 @@ fn main() -> Result<()> {
     let args = NeedleArgs::parse();
     let app_option = AppState::new(&args);
     let mut config_path = None;
+    let mut countdown = None;
+    let mut profile = None;
+    let mut check = None;
+    let mut migrate = None;
+    let mut geometry = None;
+    let mut send = None;
+    let mut new_instance = false;
 
     for opt in app_option.iter() {
         match opt {
             AppState::Help | AppState::Version => {
+                // Language of help message follows config file (or system locale)
+                if *opt == AppState::Help {
+                    i18n::set_cli_language(cli_language(&args));
+                }
                 println!("{opt}");
 
                 return Ok(());
@@ -44,11 +72,110 @@ fn main() -> Result<()> {
             AppState::ConfigFilePath(path) => {
                 config_path = Some(path.as_str());
             }
+            AppState::Countdown(duration) => {
+                countdown = Some(*duration);
+            }
+            AppState::Profile(name) => {
+                profile = Some(name.as_str());
+            }
+            AppState::CheckConfig(path) => {
+                check = Some(path.as_str());
+            }
+            AppState::MigrateConfig(path) => {
+                migrate = Some(path.as_str());
+            }
+            AppState::Geometry(value) => {
+                geometry = Some(*value);
+            }
+            AppState::Send(words) => {
+                send = Some(words);
+            }
+            AppState::NewInstance => {
+                new_instance = true;
+            }
             _ => (),
         }
     }
 
-    let config = Rc::new(RefCell::new(NeedleConfig::read(config_path)?));
+    if let Some(words) = send {
+        return ipc::send(&ipc::Command::parse(words)?);
+    }
+
+    if let Some(path) = check {
+        return check_config(if path.is_empty() {
+            config_path
+        } else {
+            Some(path)
+        });
+    }
+
+    if let Some(path) = migrate {
+        let path = if path.is_empty() {
+            config_path
+        } else {
+            Some(path)
+        };
+
+        match migrate::migrate_config(path)? {
+            Some(version) => println!(
+                "Migrated config from version {version} to {}",
+                migrate::CONFIG_VERSION
+            ),
+            None => println!("Config is up to date"),
+        }
+
+        return Ok(());
+    }
+
+    // Only one instance receives commands from `--send`
+    if !new_instance && ipc::is_running() {
+        println!("needle is already running (use --new-instance to launch another instance)");
+
+        return Ok(());
+    }
+    migrate::migrate_config(config_path)?;
+
+    let mut config = read_needle_config(config_path)?;
+    let mut app_config = AppConfig::read(config_path)?;
+
+    if let Some(name) = profile {
+        app_config.switch_profile(name, &mut config)?;
+    }
+    if countdown.is_some() {
+        app_config.timer.countdown = countdown;
+    }
+    app_config.window.drop_unremembered();
+    if let Some(geometry) = geometry.as_ref() {
+        app_config.window.apply_geometry(geometry);
+    }
+    app_config.mark_saved(&config)?;
+    i18n::set_language(app_config.language);
+    // Kept until needle exits, as fonts are loaded while running
+    let fonts = fonts::register_fonts(&app_config.fonts.files)?;
+
+    fonts
+        .files
+        .iter()
+        .for_each(|font| log::info!("Loaded font file {}", font.display()));
+
+    run(
+        Rc::new(RefCell::new(config)),
+        Rc::new(RefCell::new(app_config)),
+        !new_instance,
+    )
+}
+
+/// Language of help message set in config file and profile of `--profile`.
+/// Config is not loaded for the app with `--help`, so it is read here,
+/// falling back to the system locale if unreadable.
+fn cli_language(args: &NeedleArgs) -> i18n::Language {
+    AppConfig::read((!args.config.is_empty()).then_some(args.config.as_str()))
+        .and_then(|mut app_config| {
+            if !args.profile.is_empty() {
+                app_config.switch_profile(&args.profile, &mut NeedleConfig::default())?;
+            }
 
-    run(config)
+            Ok(app_config.language)
+        })
+        .unwrap_or_default()
 }
@@ -0,0 +1,167 @@
+// Copyright 2025 Kensuke Saito
+// SPDX-License-Identifier: MIT
+
+use crate::document::update_document;
+use anyhow::{bail, Result};
+use needle_core::NeedleConfig;
+use std::{fs, path::PathBuf};
+
+/// Version of config layout written by this release.
+///  - 1: Layout of 0.2.2 (font selection, RGB background).
+///    Files older than 0.2.2 lack only the optional `time.font` and are read as version 1.
+///  - 2: Layout of 0.2.3 (RGBA background for transparency)
+///  - 3: `version` field and application specific sections
+pub const CONFIG_VERSION: u32 = 3;
+
+const CONFIG_FILE: &str = "config.toml";
+const VERSION_KEY: &str = "version";
+
+/// Migration from version `n` to `n + 1` (starting from version 1)
+const MIGRATIONS: [fn(&mut toml::Table); CONFIG_VERSION as usize - 1] =
+    [background_alpha, version_field];
+
+/// Version of config layout.
+/// Unversioned files are detected from their layout.
+pub fn config_version(table: &toml::Table) -> Result<u32> {
+    match table.get(VERSION_KEY) {
+        Some(toml::Value::Integer(version)) if *version > 0 => Ok(*version as u32),
+        Some(version) => bail!("Invalid config version {version}"),
+        None => {
+            let background_len = table
+                .get("background_color")
+                .and_then(|color| color.as_array())
+                .map(|color| color.len());
+
+            Ok(if background_len == Some(3) { 1 } else { 2 })
+        }
+    }
+}
+
+/// Upgrade config table to `CONFIG_VERSION`.
+/// Returns the version before migration if the table has been migrated.
+pub fn migrate_table(table: &mut toml::Table) -> Result<Option<u32>> {
+    let version = config_version(table)?;
+
+    if version > CONFIG_VERSION {
+        bail!("Config version {version} is newer than supported version {CONFIG_VERSION}");
+    }
+    if version == CONFIG_VERSION {
+        return Ok(None);
+    }
+    MIGRATIONS[version as usize - 1..]
+        .iter()
+        .for_each(|migration| migration(table));
+
+    Ok(Some(version))
+}
+
+/// Migrate config file in place, keeping a backup of the original file.
+/// Returns the version before migration if the file has been migrated.
+pub fn migrate_config(path: Option<&str>) -> Result<Option<u32>> {
+    let path = match path {
+        Some(path) => PathBuf::from(path),
+        None => NeedleConfig::config_path(false, Some(CONFIG_FILE))?,
+    };
+
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let source = fs::read_to_string(&path)?;
+    let old = source.parse::<toml::Table>()?;
+    let mut table = old.clone();
+    let version = migrate_table(&mut table)?;
+
+    if let Some(version) = version {
+        let mut backup = path.clone().into_os_string();
+
+        backup.push(format!(".v{version}.bak"));
+        fs::write(&backup, &source)?;
+        fs::write(&path, update_document(&source, &old, &table)?)?;
+        log::info!(
+            "Migrated {} from version {version} to {CONFIG_VERSION} (backup: {})",
+            path.display(),
+            PathBuf::from(backup).display()
+        );
+    }
+
+    Ok(version)
+}
+
+/// 1 -> 2: Background color gained alpha channel in 0.2.3
+fn background_alpha(table: &mut toml::Table) {
+    if let Some(toml::Value::Array(color)) = table.get_mut("background_color") {
+        if color.len() == 3 {
+            color.push(toml::Value::Float(1.0));
+        }
+    }
+}
+
+/// 2 -> 3: Config files record their version
+fn version_field(table: &mut toml::Table) {
+    table.insert(VERSION_KEY.to_string(), toml::Value::Integer(3));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table(source: &str) -> toml::Table {
+        source.parse().unwrap()
+    }
+
+    #[test]
+    fn detect_config_version() {
+        let cases = [
+            ("background_color = [0.0, 0.0, 0.0]", 1),
+            ("background_color = [0.0, 0.0, 0.0, 1.0]", 2),
+            ("", 2),
+            ("version = 3", 3),
+        ];
+
+        for (source, version) in cases {
+            assert_eq!(config_version(&table(source)).unwrap(), version, "{source}");
+        }
+    }
+
+    #[test]
+    fn reject_invalid_versions() {
+        let cases = ["version = 0", "version = -1", "version = \"3\""];
+
+        for source in cases {
+            assert!(config_version(&table(source)).is_err(), "{source}");
+        }
+    }
+
+    #[test]
+    fn migrate_old_versions() {
+        let cases = [
+            ("background_color = [0.5, 0.5, 0.5]", Some(1)),
+            ("background_color = [0.5, 0.5, 0.5, 1.0]", Some(2)),
+        ];
+        let expected = table("version = 3\nbackground_color = [0.5, 0.5, 0.5, 1.0]");
+
+        for (source, version) in cases {
+            let mut migrated = table(source);
+
+            assert_eq!(migrate_table(&mut migrated).unwrap(), version, "{source}");
+            assert_eq!(migrated, expected, "{source}");
+        }
+    }
+
+    #[test]
+    fn keep_current_version() {
+        let source = "version = 3\nbackground_color = [0.5, 0.5, 0.5]";
+        let mut migrated = table(source);
+
+        assert_eq!(migrate_table(&mut migrated).unwrap(), None);
+        assert_eq!(migrated, table(source));
+    }
+
+    #[test]
+    fn reject_newer_version() {
+        let mut migrated = table("version = 4");
+
+        assert!(migrate_table(&mut migrated).is_err());
+    }
+}
@@ -0,0 +1,78 @@
+// Copyright 2025 Kensuke Saito
+// SPDX-License-Identifier: MIT
+
+use super::clock::ClockStyle;
+use crate::{AlarmConfig, FinishConfig};
+use std::time::{Duration, Instant};
+
+/// Notice shown on the clock (alarm which has fired or countdown which has finished)
+#[derive(Debug, Clone)]
+pub struct Alert {
+    label: String,
+    /// Shown in place of the time
+    text: String,
+    flash: bool,
+    color: Option<[u8; 3]>,
+    background_color: Option<[f32; 4]>,
+    start: Instant,
+    /// Shown until dismissed if `None` (also when the duration is too long to represent)
+    until: Option<Instant>,
+}
+
+impl Alert {
+    const FLASH_INTERVAL: Duration = Duration::from_millis(500);
+    /// Text color of flashing alerts without color
+    const FLASH_COLOR: [u8; 3] = [255, 64, 64];
+
+    pub fn alarm(alarm: &AlarmConfig) -> Self {
+        let start = Instant::now();
+
+        Self {
+            label: alarm.label.clone(),
+            text: String::new(),
+            flash: alarm.flash,
+            color: alarm.color,
+            background_color: alarm.background_color,
+            start,
+            until: start.checked_add(alarm.duration),
+        }
+    }
+
+    pub fn finish(finish: &FinishConfig) -> Self {
+        let start = Instant::now();
+
+        Self {
+            label: String::new(),
+            text: finish.message.clone(),
+            flash: finish.blink,
+            color: finish.color,
+            background_color: finish.background_color,
+            start,
+            until: finish
+                .notice_duration()
+                .and_then(|duration| start.checked_add(duration)),
+        }
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.until.is_some_and(|until| Instant::now() >= until)
+    }
+
+    /// Appearance of clock while the alert is shown.
+    /// Flashing alerts show their colors every other interval.
+    pub fn style(&self) -> ClockStyle {
+        let lit = !self.flash
+            || (self.start.elapsed().as_millis() / Self::FLASH_INTERVAL.as_millis()) % 2 == 0;
+        let color = match self.flash {
+            true => self.color.or(Some(Self::FLASH_COLOR)),
+            false => self.color,
+        };
+
+        ClockStyle {
+            label: (!self.label.is_empty()).then(|| self.label.clone()),
+            text: (!self.text.is_empty()).then(|| self.text.clone()),
+            color: color.filter(|_| lit),
+            background_color: self.background_color.filter(|_| lit),
+        }
+    }
+}
@@ -0,0 +1,86 @@
+// Copyright 2025 Kensuke Saito
+// SPDX-License-Identifier: MIT
+
+use super::clock::Clock;
+use crate::{BackgroundConfig, BackgroundStyle, GradientConfig};
+use needle_core::OpMode;
+use winit::dpi::PhysicalSize;
+
+/// Number of vec4 in background UBO
+pub const BACKGROUND_UBO_LEN: usize = 16;
+
+/// Uniform of background shader.
+/// Layout must match `Background` in `fs_main.wgsl`.
+///  - 0: fill color
+///  - 1: style, number of gradient stops, angle of linear gradient (radian)
+///  - 2: window size, panel size (pixels)
+///  - 3: corner radius, border width (pixels)
+///  - 4: border color
+///  - 5..7: positions of gradient stops (4 per vec4)
+///  - 7..15: colors of gradient stops
+///  - 15: elapsed seconds since launch, timer progress, timer value (seconds), clock mode
+pub type BackgroundUbo = [glm::Vec4; BACKGROUND_UBO_LEN];
+
+const STOP_POSITION_OFFSET: usize = 5;
+const STOP_COLOR_OFFSET: usize = 7;
+const TIME_OFFSET: usize = 15;
+
+/// Build uniform of background from config
+pub fn background_ubo(
+    background_color: &[f32],
+    background: &BackgroundConfig,
+    size: &PhysicalSize<u32>,
+    elapsed: f32,
+    clock: &Clock,
+) -> BackgroundUbo {
+    let mut ubo = [glm::vec4(0.0, 0.0, 0.0, 0.0); BACKGROUND_UBO_LEN];
+    let stops = background.gradient.sorted_stops();
+    // Gradient without enough stops is drawn as solid background
+    let style = match background.style {
+        BackgroundStyle::LinearGradient | BackgroundStyle::RadialGradient
+            if stops.len() < GradientConfig::MIN_STOPS =>
+        {
+            BackgroundStyle::Solid
+        }
+        style => style,
+    };
+    let (width, height) = (size.width as f32, size.height as f32);
+    let panel = &background.panel;
+
+    ubo[0] = vec4(background_color);
+    ubo[1] = glm::vec4(
+        i8::from(style) as f32,
+        stops.len() as f32,
+        background.gradient.angle.to_radians(),
+        0.0,
+    );
+    ubo[2] = glm::vec4(
+        width,
+        height,
+        width * panel.size[0] / 100.0,
+        height * panel.size[1] / 100.0,
+    );
+    ubo[3] = glm::vec4(panel.radius, panel.border_width, 0.0, 0.0);
+    ubo[4] = vec4(&panel.border_color);
+    stops.iter().enumerate().for_each(|(i, stop)| {
+        ubo[STOP_POSITION_OFFSET + i / 4][i % 4] = stop.position;
+        ubo[STOP_COLOR_OFFSET + i] = vec4(&stop.color);
+    });
+    ubo[TIME_OFFSET] = glm::vec4(
+        elapsed,
+        clock.progress(),
+        clock.timer_value().as_secs_f32(),
+        match clock.mode() {
+            OpMode::Clock => 0.0,
+            OpMode::CountUpTimer => 1.0,
+            OpMode::CountDownTimer(_) => 2.0,
+        },
+    );
+
+    ubo
+}
+
+#[inline]
+fn vec4(color: &[f32]) -> glm::Vec4 {
+    glm::vec4(color[0], color[1], color[2], color[3])
+}
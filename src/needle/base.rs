@@ -1,30 +1,115 @@
 // Copyright 2025 Kensuke Saito
 // SPDX-License-Identifier: MIT
 
-use anyhow::Result;
-use imgui::Condition;
+use super::{
+    alert::Alert,
+    background::{background_ubo, BackgroundUbo},
+    clock::{Clock, ClockStyle},
+    effect::{layer_specs, EffectLayer, TextEffect},
+    layout::{anchor, TextMetrics, TextRegion},
+    playlist::Playlist,
+    pomodoro::Pomodoro,
+    shader::{compile_wgsl, write_builtin_shaders, ShaderWatcher},
+};
+use crate::{
+    color::{blend_text_color, parse_hex, to_hex},
+    duration::{format_duration, parse_duration, split_duration},
+    format::{format_clock, format_timer},
+    i18n::{set_language, tr, Language},
+    ipc::Command,
+    process::spawn_command,
+    AlarmAction, AlarmConfig, AppConfig, BackgroundConfig, BackgroundStyle, CustomShaderConfig,
+    FinishConfig, FontsConfig, GradientConfig, GradientStop, NextTimer, OffsetUnit, OverlayConfig,
+    PlaylistConfig, PlaylistSegment, PlaylistSegmentMode, PomodoroConfig, PomodoroPhase,
+    SubtitleConfig, SubtitleContent, SubtitlePlacement, TextEffectConfig, TextOffset,
+    TimerPrecision, WindowConfig,
+};
+use anyhow::{anyhow, bail, Result};
+use chrono::{Local, NaiveDateTime};
+use imgui::{Condition, TreeNodeFlags};
 use needle_core::{
     BindGroupLayout, Buffer, FontTypes, ImguiMode, ImguiState, NeedleConfig, NeedleErr,
     NeedleError, NeedleLabel, OpMode, Position, Renderer, ShaderRenderer, ShaderRendererDescriptor,
-    State, TextRenderer, Texture, Time, TimeFormat, Ubo, Vertex,
+    State, TextRenderer, Texture, TimeFormat, Ubo, Vertex,
 };
 use std::{
     cell::RefCell,
+    fs, mem,
+    path::{Path, PathBuf},
     rc::Rc,
     sync::Arc,
     time::{Duration, Instant},
 };
-use winit::{event_loop::ActiveEventLoop, window::Window};
+use winit::{
+    dpi::{PhysicalPosition, PhysicalSize},
+    event_loop::ActiveEventLoop,
+    window::{Window, WindowLevel},
+};
+
+/// Text dragged with mouse
+#[derive(Debug, Clone, Copy)]
+enum DragTarget {
+    Time,
+    Fps,
+}
 
 pub struct NeedleBase<'a> {
     pub window: Arc<Window>,
     state: State<'a>,
-    pub imgui_state: ImguiState,
+    /// Settings menu (only the main window has one)
+    pub imgui_state: Option<ImguiState>,
     depth_texture: Texture,
     background_renderer: ShaderRenderer,
+    vert_shader_path: PathBuf,
+    frag_shader_path: PathBuf,
+    shader_error: Option<String>,
+    shader_watcher: ShaderWatcher,
     time_renderer: TextRenderer,
     fps_renderer: TextRenderer,
-    clock_info: Time,
+    subtitle_renderer: TextRenderer,
+    time_effect: TextEffect,
+    fps_effect: TextEffect,
+    time_region: TextRegion,
+    fps_region: TextRegion,
+    subtitle_region: TextRegion,
+    time_bounds: [f32; 4],
+    fps_bounds: [f32; 4],
+    time_metrics: TextMetrics,
+    fps_metrics: TextMetrics,
+    subtitle_metrics: TextMetrics,
+    clock_info: Clock,
+    /// Progress of Pomodoro mode (`None` unless enabled)
+    pomodoro: Option<Pomodoro>,
+    /// Progress of timer playlist (`None` unless enabled)
+    playlist: Option<Playlist>,
+    /// Alarm currently shown
+    alert: Option<Alert>,
+    /// Notice of finished countdown currently shown
+    finish: Option<Alert>,
+    /// Countdown has reached zero and its actions have run (until the timer is reset)
+    countdown_finished: bool,
+    /// Local time alarms have been checked until
+    alarms_checked: Option<NaiveDateTime>,
+    /// Alarms fired since last frame, whose actions outside of the window are left to the caller
+    pub fired_alarms: Vec<AlarmConfig>,
+    countdown_input: String,
+    profile_name_input: String,
+    profile_error: Option<String>,
+    quit_requested: bool,
+    pub quit: bool,
+    /// Whether the menu shows unsaved changes, and when it was last checked
+    /// (`None` to check on next frame)
+    unsaved: bool,
+    unsaved_checked: Option<Instant>,
+    menu_visible: bool,
+    imgui_wants_mouse: bool,
+    /// Whether a text field of the menu has keyboard focus
+    imgui_wants_keyboard: bool,
+    /// Whether window currently receives mouse input (false while click-through)
+    cursor_hittest: bool,
+    cursor: PhysicalPosition<f64>,
+    dragging: Option<DragTarget>,
+    start_time: Instant,
     pub current_frame: u64,
     pub next_frame: Instant,
     pub fps_update: Instant,
@@ -33,66 +118,153 @@ pub struct NeedleBase<'a> {
 }
 
 impl<'a> NeedleBase<'a> {
+    const CUSTOM_FRAGMENT_SHADER_PATH: &'static str = "shaders/spv/custom.frag.spv";
+    const ERROR_TEXT_COLOR: [f32; 4] = [1.0, 0.4, 0.4, 1.0];
+    const DIRTY_TEXT_COLOR: [f32; 4] = [1.0, 0.8, 0.3, 1.0];
+    /// Settings are compared with the saved ones at most once per interval
+    const UNSAVED_CHECK_INTERVAL: Duration = Duration::from_millis(250);
     // Imgui Tags
     const NEEDLE_IMGUI_SAVE_COUNT: usize = 2;
     const NEEDLE_IMGUI_DESCRIPTION_COUNT: usize = 4;
-    //  - Background
-    const BACKGROUND_COLOR_COUNT: usize = 4;
+    const COLOR_EDIT_TAG_COUNT: usize = 2;
     //  - Clock Timer
     const CLOCK_TIMER_FONT_ROWS: usize = 5;
-    const CLOCK_TIMER_FONT_COLOR_COUNT: usize = 3;
     const CLOCK_TIMER_POSITION_COUNT: usize = 9;
-    //  - FPS
-    const FPS_FONT_COLOR_COUNT: usize = 3;
-    const FPS_POSITION_COUNT: usize = 4;
+    const COUNTDOWN_DURATION_FIELD_COUNT: usize = 3;
+    const COUNTDOWN_PRESET_COUNT: usize = 3;
+    const POMODORO_PHASE_TAG_COUNT: usize = 7;
+    const PLAYLIST_SEGMENT_TAG_COUNT: usize = 6;
+    const FINISH_COLOR_TAG_COUNT: usize = 6;
+    //  - Layout
+    const TEXT_OFFSET_TAG_COUNT: usize = 3;
 
-    /// Create new instance of new Needle primary application logic
+    /// Create new instance of new Needle primary application logic.
+    /// Only one window can have the settings menu (`menu`) as ImGui has a single context.
     pub fn new(
         event_loop: &ActiveEventLoop,
         config: Rc<RefCell<NeedleConfig>>,
+        app_config: &AppConfig,
+        menu: bool,
         title: &str,
         vert_shader_path: &str,
         frag_shader_path: &str,
     ) -> Result<Self> {
         let window = {
-            let attr = Window::default_attributes()
+            let overlay = &app_config.overlay;
+            let mut attr = Window::default_attributes()
                 .with_title(title)
                 .with_resizable(true)
-                .with_transparent(true);
+                .with_transparent(true)
+                .with_decorations(overlay.decorations)
+                .with_window_level(Self::window_level(overlay));
+
+            #[cfg(target_os = "windows")]
+            {
+                use winit::platform::windows::WindowAttributesExtWindows;
+
+                attr = attr.with_skip_taskbar(overlay.skip_taskbar);
+            }
+            #[cfg(target_os = "linux")]
+            if overlay.skip_taskbar {
+                use winit::platform::x11::{WindowAttributesExtX11, WindowType};
+
+                // Taskbars of X11 do not list utility windows
+                attr = attr.with_x11_window_type(vec![WindowType::Utility]);
+            }
+
+            if let Some([width, height]) = app_config.window.size {
+                attr = attr.with_inner_size(PhysicalSize::new(width, height));
+            }
+            if let Some(position) = Self::window_position(event_loop, &app_config.window) {
+                attr = attr.with_position(position);
+            }
+
             let window = event_loop.create_window(attr)?;
 
             Arc::new(window)
         };
         let state = pollster::block_on(State::new(window.clone()))?;
-        let imgui_state = ImguiState::new(window.clone(), config.clone(), &state);
+        let imgui_state = menu.then(|| ImguiState::new(window.clone(), config.clone(), &state));
         let depth_texture = Texture::create_depth_texture(
             state.device(),
             state.surface_config(),
             NeedleLabel::Texture("Depth"),
         );
-        let (background, time, fps) = Self::create_renderers(
-            window.clone(),
-            config.clone(),
+        let vert_shader_path = NeedleConfig::config_path(false, Some(vert_shader_path))?;
+        let frag_shader_path = NeedleConfig::config_path(false, Some(frag_shader_path))?;
+        let background = Self::create_background_renderer(
+            &config.borrow(),
             &state,
-            vert_shader_path,
-            frag_shader_path,
+            &vert_shader_path,
+            &frag_shader_path,
         )?;
+        let (time, fps, subtitle) =
+            Self::create_renderers(window.clone(), config.clone(), app_config, &state)?;
+        let mut clock_info = Clock::new(config.borrow().time.format);
 
-        Ok(Self {
+        if let Some(duration) = app_config.timer.countdown {
+            clock_info.set_mode(OpMode::CountDownTimer(duration));
+        }
+
+        let mut base = Self {
             window,
             state,
             imgui_state,
             depth_texture,
             background_renderer: background,
+            vert_shader_path,
+            frag_shader_path,
+            shader_error: None,
+            shader_watcher: ShaderWatcher::default(),
             time_renderer: time,
             fps_renderer: fps,
-            clock_info: Time::new(config.borrow().time.format),
+            subtitle_renderer: subtitle,
+            time_effect: TextEffect::default(),
+            fps_effect: TextEffect::default(),
+            time_region: TextRegion::new(&window.inner_size()),
+            fps_region: TextRegion::new(&window.inner_size()),
+            subtitle_region: TextRegion::new(&window.inner_size()),
+            time_bounds: [0.0; 4],
+            fps_bounds: [0.0; 4],
+            time_metrics: TextMetrics::default(),
+            fps_metrics: TextMetrics::default(),
+            subtitle_metrics: TextMetrics::default(),
+            clock_info,
+            pomodoro: None,
+            playlist: None,
+            alert: None,
+            finish: None,
+            countdown_finished: false,
+            alarms_checked: None,
+            fired_alarms: Vec::new(),
+            countdown_input: String::new(),
+            profile_name_input: String::new(),
+            profile_error: None,
+            quit_requested: false,
+            quit: false,
+            unsaved: false,
+            unsaved_checked: None,
+            menu_visible: false,
+            imgui_wants_mouse: false,
+            imgui_wants_keyboard: false,
+            cursor_hittest: true,
+            cursor: PhysicalPosition::new(0.0, 0.0),
+            dragging: None,
+            start_time: Instant::now(),
             current_frame: 0,
             next_frame: Instant::now(),
             fps_limit: Duration::from_secs_f64(1.0 / config.borrow().fps.frame_limit as f64),
             fps_update_limit: Duration::from_secs_f64(1.0),
             fps_update: Instant::now(),
-        })
+        };
+
+        if app_config.background.shader.enable {
+            base.load_background_shader(&config.borrow(), &app_config.background.shader, false);
+        } else {
+            base.watch_background_shader(&app_config.background.shader);
+        }
+
+        Ok(base)
     }
 
     /// Start count down/count up timer.
@@ -107,6 +279,542 @@ impl<'a> NeedleBase<'a> {
         }
     }
 
+    /// Apply command sent with `--send`.
+    /// Quitting asks for confirmation in the menu if there are unsaved changes.
+    pub fn control(
+        &mut self,
+        command: Command,
+        config: &NeedleConfig,
+        app_config: &AppConfig,
+    ) -> Result<()> {
+        match command {
+            Command::Start if self.clock_info.is_running() => Ok(()),
+            Command::Stop if !self.clock_info.is_running() => Ok(()),
+            Command::Start | Command::Stop | Command::Toggle => {
+                self.start_clock().map_err(|e| anyhow!("{e}"))
+            }
+            Command::Reset => {
+                self.clock_info.set_mode(self.clock_info.mode());
+                Ok(())
+            }
+            Command::Clock => {
+                self.clock_info.set_mode(OpMode::Clock);
+                Ok(())
+            }
+            Command::CountUp => {
+                self.clock_info.set_mode(OpMode::CountUpTimer);
+                Ok(())
+            }
+            Command::Countdown(duration) => {
+                self.clock_info.set_mode(OpMode::CountDownTimer(
+                    duration.or(app_config.timer.countdown).unwrap_or_default(),
+                ));
+                Ok(())
+            }
+            Command::Skip => {
+                self.skip_phase(app_config);
+                Ok(())
+            }
+            // Subscriptions are handled by the control interface
+            Command::Events => Ok(()),
+            Command::Menu => {
+                self.show_menu();
+                Ok(())
+            }
+            // Quitting is left to the confirmation in the menu if there are unsaved changes
+            Command::Quit => {
+                self.request_quit(config, app_config);
+                if !self.quit {
+                    bail!("There are unsaved changes (confirm quitting in the menu)");
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Whether text is being typed into the menu (hotkeys are ignored meanwhile)
+    pub fn wants_keyboard(&self) -> bool {
+        self.imgui_wants_keyboard
+    }
+
+    /// Skip to next phase of Pomodoro mode or next segment of playlist,
+    /// keeping the timer running if it is
+    pub fn skip_phase(&mut self, app_config: &AppConfig) {
+        let running = self.clock_info.is_running();
+
+        if let Some(pomodoro) = self.pomodoro.as_mut() {
+            pomodoro.next(app_config.pomodoro.rounds);
+            self.start_phase(&app_config.pomodoro, running);
+        } else if let Some(playlist) = self.playlist.as_mut() {
+            if playlist.next(app_config.playlist.segments.len()) {
+                self.start_segment(&app_config.playlist, running);
+            }
+        }
+    }
+
+    /// Set up countdown of current Pomodoro phase
+    fn start_phase(&mut self, config: &PomodoroConfig, start: bool) {
+        if let Some(pomodoro) = self.pomodoro.as_ref() {
+            self.clock_info.set_mode(OpMode::CountDownTimer(
+                config.phase(pomodoro.phase()).duration,
+            ));
+            if start {
+                self.clock_info.toggle_timer();
+            }
+        }
+    }
+
+    /// Enter/Leave Pomodoro mode following config, and move to next phase when a phase ends.
+    /// Switching to another clock mode leaves Pomodoro mode (returns true).
+    fn update_pomodoro(&mut self, config: &mut PomodoroConfig) -> bool {
+        let countdown = matches!(self.clock_info.mode(), OpMode::CountDownTimer(_));
+
+        match self.pomodoro.as_mut() {
+            None if config.enable && self.playlist.is_none() => {
+                self.pomodoro = Some(Pomodoro::default());
+                self.start_phase(config, false);
+            }
+            Some(_) if !config.enable => {
+                self.pomodoro = None;
+                self.clock_info.set_mode(OpMode::Clock);
+            }
+            Some(_) if !countdown => {
+                self.pomodoro = None;
+                config.enable = false;
+
+                return true;
+            }
+            Some(pomodoro)
+                if self.clock_info.is_running() && self.clock_info.timer_value().is_zero() =>
+            {
+                pomodoro.next(config.rounds);
+                self.start_phase(config, config.auto_start);
+            }
+            _ => (),
+        }
+
+        false
+    }
+
+    /// Set up timer of current playlist segment
+    fn start_segment(&mut self, config: &PlaylistConfig, start: bool) {
+        if let Some(segment) = self
+            .playlist
+            .and_then(|playlist| config.segments.get(playlist.segment()))
+        {
+            self.clock_info.set_mode(segment.op_mode());
+            if start {
+                self.clock_info.toggle_timer();
+            }
+        }
+    }
+
+    /// Enter/Leave playlist following config, and move to next segment when a segment ends.
+    /// Timer stops at the end of the last segment.
+    /// Switching to another clock mode leaves the playlist (returns true).
+    fn update_playlist(&mut self, config: &mut PlaylistConfig) -> bool {
+        let (in_segment, finished) = match self
+            .playlist
+            .and_then(|playlist| config.segments.get(playlist.segment()))
+        {
+            Some(segment) => (
+                mem::discriminant(&segment.op_mode()) == mem::discriminant(&self.clock_info.mode()),
+                self.clock_info.is_running()
+                    && Playlist::is_finished(segment, self.clock_info.timer_value()),
+            ),
+            None => (false, false),
+        };
+
+        match self.playlist.as_mut() {
+            None if config.enable && !config.segments.is_empty() && self.pomodoro.is_none() => {
+                self.playlist = Some(Playlist::default());
+                self.start_segment(config, false);
+            }
+            Some(_) if !config.enable => {
+                self.playlist = None;
+                self.clock_info.set_mode(OpMode::Clock);
+            }
+            Some(_) if !in_segment => {
+                self.playlist = None;
+                config.enable = false;
+
+                return true;
+            }
+            Some(playlist) if finished => {
+                if playlist.next(config.segments.len()) {
+                    self.start_segment(config, config.auto_advance);
+                } else {
+                    self.clock_info.toggle_timer();
+                }
+            }
+            _ => (),
+        }
+
+        false
+    }
+
+    /// Fire alarms due since last frame while in clock mode, and hide alarm shown when it is over.
+    /// Alarms are not fired retroactively after leaving clock mode.
+    fn update_alarms(&mut self, alarms: &[AlarmConfig]) {
+        let now = Local::now().naive_local();
+        let checked = self.alarms_checked.replace(now);
+
+        if let (Some(checked), OpMode::Clock) = (checked, self.clock_info.mode()) {
+            for alarm in alarms.iter().filter(|alarm| alarm.is_due(checked, now)) {
+                log::info!("Alarm \"{}\" has fired", alarm.label);
+                self.alert = Some(Alert::alarm(alarm));
+                if let AlarmAction::Countdown(duration) = alarm.action {
+                    self.clock_info.set_mode(OpMode::CountDownTimer(duration));
+                    self.clock_info.toggle_timer();
+                }
+                self.fired_alarms.push(alarm.clone());
+            }
+        }
+        if self.alert.as_ref().is_some_and(Alert::is_over) {
+            self.alert = None;
+        }
+    }
+
+    /// Run actions when countdown timer reaches zero, and start next timer when the notice is over.
+    /// Notice is dismissed when the timer is reset or switched.
+    fn update_finish(&mut self, app_config: &AppConfig) {
+        let finish = &app_config.timer.finish;
+        let OpMode::CountDownTimer(duration) = self.clock_info.mode() else {
+            self.finish = None;
+            self.countdown_finished = false;
+            return;
+        };
+
+        if !self.clock_info.timer_value().is_zero() {
+            self.finish = None;
+            self.countdown_finished = false;
+        } else if !self.countdown_finished
+            && self.clock_info.is_running()
+            && self.pomodoro.is_none()
+            && self.playlist.is_none()
+        {
+            log::info!("Countdown of {} has finished", format_duration(&duration));
+            self.countdown_finished = true;
+            // Timer stays at zero until reset
+            self.clock_info.toggle_timer();
+            Self::finish_actions(finish, duration, app_config.profiles.active.as_deref());
+            self.finish = Some(Alert::finish(finish));
+        }
+        if self.finish.as_ref().is_some_and(Alert::is_over) {
+            self.finish = None;
+            self.start_next_timer(finish.start, duration);
+        }
+    }
+
+    /// Start timer following finished countdown of `duration`
+    fn start_next_timer(&mut self, next: NextTimer, duration: Duration) {
+        let mode = match next {
+            NextTimer::None => return,
+            NextTimer::Countdown(duration) => OpMode::CountDownTimer(duration),
+            NextTimer::CountUp => OpMode::CountUpTimer,
+            NextTimer::Repeat => OpMode::CountDownTimer(duration),
+        };
+
+        self.clock_info.set_mode(mode);
+        self.clock_info.toggle_timer();
+    }
+
+    /// Run command and write marker file for finished countdown
+    fn finish_actions(finish: &FinishConfig, duration: Duration, profile: Option<&str>) {
+        let finished_at = Local::now().to_rfc3339();
+        let duration_secs = duration.as_secs().to_string();
+        let duration = format_duration(&duration);
+        let envs = [
+            ("NEEDLE_TIMER_DURATION", duration.as_str()),
+            ("NEEDLE_TIMER_DURATION_SECS", duration_secs.as_str()),
+            ("NEEDLE_TIMER_FINISHED_AT", finished_at.as_str()),
+            ("NEEDLE_TIMER_PROFILE", profile.unwrap_or_default()),
+        ];
+
+        if !finish.command.trim().is_empty() {
+            if let Err(e) = spawn_command(&finish.command, &envs) {
+                log::error!("{e}");
+            }
+        }
+        if !finish.marker_file.trim().is_empty() {
+            let contents = envs
+                .iter()
+                .map(|(key, value)| format!("{key}={value}\n"))
+                .collect::<String>();
+
+            if let Err(e) = Self::write_marker_file(&finish.marker_file, &contents) {
+                log::error!("{e}");
+            }
+        }
+    }
+
+    fn write_marker_file(path: &str, contents: &str) -> Result<()> {
+        let path = match PathBuf::from(path) {
+            path if path.is_absolute() => path,
+            _ => NeedleConfig::config_path(false, Some(path))?,
+        };
+
+        fs::write(path, contents)?;
+
+        Ok(())
+    }
+
+    /// Overrides of clock appearance by alarms, finished countdown, Pomodoro mode and playlist
+    fn clock_style(&self, app_config: &AppConfig) -> ClockStyle {
+        if let Some(alert) = self.alert.as_ref().or(self.finish.as_ref()) {
+            return alert.style();
+        }
+        if let Some(pomodoro) = self.pomodoro.as_ref() {
+            let phase = app_config.pomodoro.phase(pomodoro.phase());
+
+            return ClockStyle {
+                label: Some(pomodoro.label(app_config.pomodoro.rounds)),
+                color: phase.color,
+                background_color: phase.background_color,
+                ..ClockStyle::default()
+            };
+        }
+        if let Some(segment) = self
+            .playlist
+            .and_then(|playlist| app_config.playlist.segments.get(playlist.segment()))
+        {
+            return ClockStyle {
+                label: Playlist::label(segment),
+                color: segment.color,
+                background_color: segment.background_color,
+                ..ClockStyle::default()
+            };
+        }
+
+        ClockStyle::default()
+    }
+
+    /// Request to quit needle.
+    /// Asks for confirmation in the menu if there are unsaved changes.
+    pub fn request_quit(&mut self, config: &NeedleConfig, app_config: &AppConfig) {
+        let Some(imgui_state) = self.imgui_state.as_mut() else {
+            self.quit = true;
+
+            return;
+        };
+
+        if !app_config.is_dirty(config) {
+            self.quit = true;
+
+            return;
+        }
+
+        self.quit_requested = true;
+        if !self.menu_visible {
+            imgui_state.toggle_imgui();
+        }
+    }
+
+    /// Open settings menu and focus the window, so the menu can be used without the hotkey
+    fn show_menu(&mut self) {
+        if let Some(imgui_state) = self.imgui_state.as_mut() {
+            if !self.menu_visible {
+                imgui_state.toggle_imgui();
+            }
+            self.window.focus_window();
+        }
+    }
+
+    /// Switch to next profile
+    pub fn next_profile(&mut self, config: &mut NeedleConfig, app_config: &mut AppConfig) {
+        if let Some(name) = app_config.profiles.next().map(str::to_string) {
+            self.switch_profile(&name, config, app_config);
+        }
+    }
+
+    /// Replace current settings with profile `name`
+    fn switch_profile(
+        &mut self,
+        name: &str,
+        config: &mut NeedleConfig,
+        app_config: &mut AppConfig,
+    ) {
+        match app_config.switch_profile(name, config) {
+            Ok(()) => {
+                self.profile_error = None;
+                self.apply_config(config, app_config);
+            }
+            Err(e) => {
+                log::error!("{e}");
+                self.profile_error = Some(e.to_string());
+            }
+        }
+    }
+
+    /// Save window geometry to config file if it should be remembered
+    pub fn save_window_geometry(&self, app_config: &mut AppConfig) {
+        if app_config.window.remember {
+            let geometry = Self::window_geometry(&self.window);
+
+            if let Err(e) = app_config.save_window(geometry) {
+                log::error!("{e}");
+            }
+        }
+    }
+
+    /// Save geometry of additional window `index` to config file if it should be remembered
+    pub fn save_clock_window_geometry(&self, index: usize, app_config: &mut AppConfig) {
+        if app_config
+            .windows
+            .get(index)
+            .is_some_and(|window| window.window.remember)
+        {
+            let geometry = Self::window_geometry(&self.window);
+
+            if let Err(e) = app_config.save_clock_window(index, geometry) {
+                log::error!("{e}");
+            }
+        }
+    }
+
+    /// Apply always-on-top, decorations and skip-taskbar to the window
+    fn apply_overlay(&self, overlay: &OverlayConfig) {
+        self.window.set_window_level(Self::window_level(overlay));
+        self.window.set_decorations(overlay.decorations);
+        #[cfg(target_os = "windows")]
+        {
+            use winit::platform::windows::WindowExtWindows;
+
+            self.window.set_skip_taskbar(overlay.skip_taskbar);
+        }
+    }
+
+    fn window_level(overlay: &OverlayConfig) -> WindowLevel {
+        if overlay.always_on_top {
+            WindowLevel::AlwaysOnTop
+        } else {
+            WindowLevel::Normal
+        }
+    }
+
+    /// Let mouse input pass through the window in click-through mode,
+    /// except while the settings menu is open
+    fn update_cursor_hittest(&mut self, overlay: &OverlayConfig) {
+        let hittest = !overlay.click_through || self.menu_visible;
+
+        if hittest != self.cursor_hittest {
+            match self.window.set_cursor_hittest(hittest) {
+                Ok(()) => self.cursor_hittest = hittest,
+                Err(e) => log::error!("{e}"),
+            }
+        }
+    }
+
+    /// Saved window position in screen coordinates.
+    /// Position relative to a monitor which is no longer connected is ignored.
+    fn window_position(
+        event_loop: &ActiveEventLoop,
+        window: &WindowConfig,
+    ) -> Option<PhysicalPosition<i32>> {
+        let [x, y] = window.position?;
+        let origin = match window.monitor.as_ref() {
+            Some(name) => event_loop
+                .available_monitors()
+                .find(|monitor| monitor.name().as_ref() == Some(name))?
+                .position(),
+            None => PhysicalPosition::new(0, 0),
+        };
+
+        Some(PhysicalPosition::new(origin.x + x, origin.y + y))
+    }
+
+    /// Current window geometry to remember
+    /// (position is stored relative to the current monitor)
+    fn window_geometry(window: &Window) -> WindowConfig {
+        let size = window.inner_size();
+        let monitor = window
+            .current_monitor()
+            .and_then(|monitor| Some((monitor.name()?, monitor.position())));
+        let position = window.outer_position().ok().map(|position| {
+            let origin = monitor
+                .as_ref()
+                .map(|(_, origin)| *origin)
+                .unwrap_or(PhysicalPosition::new(0, 0));
+
+            [position.x - origin.x, position.y - origin.y]
+        });
+
+        WindowConfig {
+            remember: true,
+            size: Some([size.width, size.height]),
+            position,
+            monitor: monitor.map(|(name, _)| name),
+        }
+    }
+
+    /// Re-apply fonts, clock format, frame limit and background shader
+    /// after settings have been replaced
+    fn apply_config(&mut self, config: &NeedleConfig, app_config: &AppConfig) {
+        let fonts = [
+            (
+                &mut self.time_renderer,
+                &mut self.time_region,
+                config.time.font.as_ref(),
+            ),
+            (
+                &mut self.subtitle_renderer,
+                &mut self.subtitle_region,
+                app_config.subtitle.font.as_ref(),
+            ),
+            (
+                &mut self.fps_renderer,
+                &mut self.fps_region,
+                app_config.fonts.fps.as_ref(),
+            ),
+        ];
+
+        for (renderer, region, font) in fonts {
+            match font {
+                Some(font) => {
+                    if let Err(e) = renderer.set_font(font) {
+                        log::error!("{e}");
+                    }
+                }
+                // Default font can only be restored by creating the renderer again
+                None => match Self::create_text_renderer(&self.window, config, &self.state, None) {
+                    Ok(default) => {
+                        *renderer = default;
+                        *region = TextRegion::new(&self.window.inner_size());
+                    }
+                    Err(e) => log::error!("{e}"),
+                },
+            }
+        }
+        for renderer in [&mut self.time_renderer, &mut self.fps_renderer] {
+            if let Err(e) = Self::query_fonts(renderer, &app_config.fonts) {
+                log::error!("{e}");
+            }
+        }
+        self.clock_info.set_format(config.time.format);
+        self.fps_limit = Duration::from_secs_f64(1.0 / config.fps.frame_limit as f64);
+        self.load_background_shader(config, &app_config.background.shader, false);
+        self.apply_overlay(&app_config.overlay);
+        set_language(app_config.language);
+    }
+
+    /// Interval until next frame.
+    /// Running timers showing sub-second digits are redrawn as often as the digits change,
+    /// up to the refresh rate of the monitor.
+    pub fn frame_interval(&self, precision: TimerPrecision) -> Duration {
+        if !self.clock_info.is_running() || precision == TimerPrecision::Seconds {
+            return self.fps_limit;
+        }
+
+        let refresh_interval = self
+            .window
+            .current_monitor()
+            .and_then(|monitor| monitor.refresh_rate_millihertz())
+            .map(|millihertz| Duration::from_secs_f64(1000.0 / millihertz as f64))
+            .unwrap_or_default();
+
+        self.fps_limit.min(precision.unit().max(refresh_interval))
+    }
+
     /// Resize render surface to new window size
     pub fn resize(&mut self, size: &winit::dpi::PhysicalSize<u32>) {
         if (size.width > 0) && (size.height > 0) {
@@ -116,20 +824,69 @@ impl<'a> NeedleBase<'a> {
                 self.state.surface_config(),
                 NeedleLabel::Texture("Depth"),
             );
-            self.time_renderer.resize(size);
-            self.fps_renderer.resize(size);
         }
     }
 
+    /// Update cursor position and move text being dragged
+    pub fn cursor_moved(&mut self, position: PhysicalPosition<f64>, app_config: &mut AppConfig) {
+        let delta = [
+            (position.x - self.cursor.x) as f32,
+            (position.y - self.cursor.y) as f32,
+        ];
+        let size = self.surface_size();
+
+        self.cursor = position;
+        match self.dragging {
+            Some(DragTarget::Time) => app_config.layout.time.move_by(delta, &size),
+            Some(DragTarget::Fps) => app_config.layout.fps.move_by(delta, &size),
+            None => (),
+        }
+    }
+
+    /// Start/Stop dragging text under the cursor.
+    /// Text can only be dragged while settings menu is open.
+    pub fn drag_text(&mut self, pressed: bool) {
+        let (x, y) = (self.cursor.x as f32, self.cursor.y as f32);
+        let contains = |bounds: [f32; 4]| {
+            (bounds[0]..=bounds[2]).contains(&x) && (bounds[1]..=bounds[3]).contains(&y)
+        };
+
+        self.dragging = if !pressed || !self.menu_visible || self.imgui_wants_mouse {
+            None
+        } else if contains(self.time_bounds) {
+            Some(DragTarget::Time)
+        } else if contains(self.fps_bounds) {
+            Some(DragTarget::Fps)
+        } else {
+            None
+        };
+    }
+
     /// Render single frame of all objects in needle
-    pub fn render(&mut self, config: &mut NeedleConfig) -> Result<()> {
+    pub fn render(&mut self, config: &mut NeedleConfig, app_config: &mut AppConfig) -> Result<()> {
+        const POMODORO_KEY: &str = "pomodoro";
+        const PLAYLIST_KEY: &str = "playlist";
+
         self.state.device().poll(wgpu::PollType::Wait)?;
         let texture = self.state.get_current_texture()?;
         let view = texture
             .texture
             .create_view(&wgpu::TextureViewDescriptor::default());
-        self.update_imgui(config)?;
-        self.update(config)?;
+        self.update_imgui(config, app_config)?;
+        self.update_cursor_hittest(&app_config.overlay);
+        if self.update_pomodoro(&mut app_config.pomodoro) {
+            app_config.keep_saved_enable(POMODORO_KEY, false);
+        }
+        if self.update_playlist(&mut app_config.playlist) {
+            app_config.keep_saved_enable(PLAYLIST_KEY, false);
+        }
+        self.update_alarms(&app_config.alarms);
+        self.update_finish(app_config);
+        if self.shader_watcher.changed() {
+            self.load_background_shader(config, &app_config.background.shader, true);
+        }
+        self.sync_text_effects(config, app_config)?;
+        self.update(config, app_config)?;
         self.window.pre_present_notify();
         if let Err(err) = self.render_needle(&view) {
             match err {
@@ -149,7 +906,9 @@ impl<'a> NeedleBase<'a> {
             }
         }
 
-        self.imgui_state.render(&self.state, &view)?;
+        if let Some(imgui_state) = self.imgui_state.as_mut() {
+            imgui_state.render(&self.state, &view)?;
+        }
         self.state.device().poll(wgpu::PollType::Wait)?;
         texture.present();
 
@@ -157,36 +916,158 @@ impl<'a> NeedleBase<'a> {
     }
 
     /// Update render content for new frame
-    fn update(&mut self, config: &NeedleConfig) -> NeedleErr<()> {
+    fn update(&mut self, config: &NeedleConfig, app_config: &AppConfig) -> NeedleErr<()> {
         const TEXT_RENDERER_MARGIN: f32 = 5.0;
 
-        let background = glm::vec4(
-            config.background_color[0],
-            config.background_color[1],
-            config.background_color[2],
-            config.background_color[3],
+        let size = self.surface_size();
+        let style = self.clock_style(app_config);
+        let background_color: &[f32] = match style.background_color.as_ref() {
+            Some(color) => color,
+            None => &config.background_color,
+        };
+        let background = background_ubo(
+            background_color,
+            &app_config.background,
+            &size,
+            self.start_time.elapsed().as_secs_f32(),
+            &self.clock_info,
         );
 
         self.background_renderer
             .write_buffer(&background, self.state.queue())?;
-        self.time_renderer.set_text(&self.clock_info.current_time());
-        self.time_renderer.set_config(&config.time.config);
+
+        // Clock
+        let time = match style.text.as_ref() {
+            Some(text) => text.clone(),
+            None => self
+                .clock_info
+                .current_time(&app_config.custom_format, app_config.timer.precision),
+        };
+        let time = match style.label.as_ref() {
+            Some(label) => format!("{label} {time}"),
+            None => time,
+        };
+        let time_offset = app_config.layout.time.pixels(&size);
+        let time_region = TextRegion::with_offset(config.time.config.position, time_offset, &size);
+
+        Self::layout_text(&mut self.time_renderer, &mut self.time_region, time_region);
+        self.time_metrics.set_font(config.time.font.as_deref());
+        self.time_bounds = time_region.text_bounds(
+            config.time.config.position,
+            &time,
+            config.time.config.scale,
+            TEXT_RENDERER_MARGIN,
+            &self.time_metrics,
+        );
+        let mut time_config = config.time.config.clone();
+
+        time_config.color = blend_text_color(
+            style.color.unwrap_or(time_config.color),
+            app_config.text_alpha.time,
+            background_color,
+        );
+        self.time_renderer.set_text(&time);
+        self.time_renderer.set_config(&time_config);
         self.time_renderer.update(&self.state);
         self.time_renderer
             .prepare(TEXT_RENDERER_MARGIN, &self.state)?;
+        Self::update_text_effect(
+            &mut self.time_effect,
+            &self.state,
+            &time,
+            config.time.config.position,
+            time_offset,
+            &size,
+            TEXT_RENDERER_MARGIN,
+            |renderer, color| {
+                let mut text_config = config.time.config.clone();
+
+                text_config.color = color;
+                renderer.set_config(&text_config);
+            },
+        )?;
+
+        // Subtitle
+        let subtitle = &app_config.subtitle;
+        let subtitle_region = if subtitle.enable {
+            self.subtitle_metrics.set_font(subtitle.font.as_deref());
+            Self::subtitle_region(
+                config,
+                subtitle,
+                time_offset,
+                &size,
+                [&self.time_metrics, &self.subtitle_metrics],
+            )
+        } else {
+            TextRegion::new(&size)
+        };
+        let mut subtitle_config = config.time.config.clone();
+
+        Self::layout_text(
+            &mut self.subtitle_renderer,
+            &mut self.subtitle_region,
+            subtitle_region,
+        );
+        if subtitle.enable {
+            self.subtitle_renderer.set_text(&subtitle.text());
+        } else {
+            self.subtitle_renderer.set_text("");
+        }
+        subtitle_config.color = subtitle.color;
+        subtitle_config.scale = subtitle.scale;
+        self.subtitle_renderer.set_config(&subtitle_config);
+        self.subtitle_renderer.update(&self.state);
+        self.subtitle_renderer
+            .prepare(TEXT_RENDERER_MARGIN, &self.state)?;
 
-        if config.fps.enable {
-            self.fps_renderer.set_text(&format!(
+        // FPS
+        let fps = if config.fps.enable {
+            format!(
                 "{:.3}",
                 config.fps.frame_limit as f64 - 1.0 / self.current_frame as f64
-            ));
+            )
         } else {
-            self.fps_renderer.set_text("");
-        }
-        self.fps_renderer.set_config(&config.fps.config);
+            String::new()
+        };
+        let fps_offset = app_config.layout.fps.pixels(&size);
+        let fps_region = TextRegion::with_offset(config.fps.config.position, fps_offset, &size);
+
+        Self::layout_text(&mut self.fps_renderer, &mut self.fps_region, fps_region);
+        self.fps_metrics.set_font(app_config.fonts.fps.as_deref());
+        self.fps_bounds = fps_region.text_bounds(
+            config.fps.config.position,
+            &fps,
+            config.fps.config.scale,
+            TEXT_RENDERER_MARGIN,
+            &self.fps_metrics,
+        );
+        let mut fps_config = config.fps.config.clone();
+
+        fps_config.color = blend_text_color(
+            fps_config.color,
+            app_config.text_alpha.fps,
+            background_color,
+        );
+        self.fps_renderer.set_text(&fps);
+        self.fps_renderer.set_config(&fps_config);
         self.fps_renderer.update(&self.state);
         self.fps_renderer
             .prepare(TEXT_RENDERER_MARGIN, &self.state)?;
+        Self::update_text_effect(
+            &mut self.fps_effect,
+            &self.state,
+            &fps,
+            config.fps.config.position,
+            fps_offset,
+            &size,
+            TEXT_RENDERER_MARGIN,
+            |renderer, color| {
+                let mut text_config = config.fps.config.clone();
+
+                text_config.color = color;
+                renderer.set_config(&text_config);
+            },
+        )?;
 
         let event = self.state.queue().submit([]);
 
@@ -200,32 +1081,331 @@ impl<'a> NeedleBase<'a> {
         }
     }
 
-    /// Update Imgui UI for needle
-    fn update_imgui(&mut self, config: &mut NeedleConfig) -> NeedleErr<()> {
-        // Imgui Tags
-        const NEEDLE_IMGUI_WINDOW_TITLE: &str = "Needle Settings";
-        const NEEDLE_IMGUI_WINDOW_SIZE: [f32; 2] = [800.0, 600.0];
-        const NEEDLE_IMGUI_SETTINGS: &str = "Settings";
-        const NEEDLE_IMGUI_SAVE: &str = "Save";
-        //  - Background
-        const BACKGROUND_COLOR: &str = "Color:";
-        //  - Clock Timer
-        const CLOCK_TIMER_FONT: &str = "Font";
-        const CLOCK_TIMER_FONT_COLOR: &str = "Font Color:";
-        const CLOCK_TIMER_FONT_SCALE: &str = "Font Scale";
-        const CLOCK_TIMER_POSITION: &str = "Clock Position";
-        const CLOCK_TIMER_MODE: &str = "Mode:";
-        const CLOCK_TIMER_FORMAT_MODE: &str = "Format Mode";
-        const CLOCK_TIMER_CLOCK_MODE: &str = "Clock Mode";
+    /// Load background shader.
+    /// On failure, the previous pipeline is kept if `keep_previous` is set,
+    /// otherwise falls back to the built-in shader.
+    fn load_background_shader(
+        &mut self,
+        config: &NeedleConfig,
+        shader: &CustomShaderConfig,
+        keep_previous: bool,
+    ) {
+        self.watch_background_shader(shader);
+        self.shader_error = None;
+
+        let renderer = if shader.enable {
+            self.create_custom_background_renderer(config, shader)
+        } else {
+            self.create_builtin_background_renderer(config)
+        };
+
+        match renderer {
+            Ok(renderer) => self.background_renderer = renderer,
+            Err(e) => {
+                log::error!("{e}");
+                self.shader_error = Some(e.to_string());
+                if !keep_previous && shader.enable {
+                    match self.create_builtin_background_renderer(config) {
+                        Ok(renderer) => self.background_renderer = renderer,
+                        Err(e) => log::error!("{e}"),
+                    }
+                }
+            }
+        }
+    }
+
+    /// Watch shader files used for background for changes
+    fn watch_background_shader(&mut self, shader: &CustomShaderConfig) {
+        let frag_shader_path = if shader.enable {
+            match Self::custom_shader_path(shader) {
+                Ok(path) => path,
+                Err(e) => {
+                    log::error!("{e}");
+
+                    return;
+                }
+            }
+        } else {
+            self.frag_shader_path.clone()
+        };
+
+        self.shader_watcher =
+            ShaderWatcher::new(vec![self.vert_shader_path.clone(), frag_shader_path]);
+    }
+
+    /// Path to user supplied WGSL shader.
+    /// Relative paths are resolved from the config directory.
+    fn custom_shader_path(shader: &CustomShaderConfig) -> Result<PathBuf> {
+        Ok(match PathBuf::from(&shader.path) {
+            path if path.is_absolute() => path,
+            _ => NeedleConfig::config_path(false, Some(&shader.path))?,
+        })
+    }
+
+    /// Compile user supplied WGSL shader and create background renderer with it
+    fn create_custom_background_renderer(
+        &self,
+        config: &NeedleConfig,
+        shader: &CustomShaderConfig,
+    ) -> Result<ShaderRenderer> {
+        let source_path = Self::custom_shader_path(shader)?;
+        let output_path =
+            NeedleConfig::config_path(false, Some(Self::CUSTOM_FRAGMENT_SHADER_PATH))?;
+
+        compile_wgsl(&source_path, &output_path)?;
+
+        self.create_checked_background_renderer(config, &output_path)
+    }
+
+    /// Compile built-in shaders from embedded sources and create background renderer with them
+    fn create_builtin_background_renderer(&self, config: &NeedleConfig) -> Result<ShaderRenderer> {
+        write_builtin_shaders(&self.vert_shader_path, &self.frag_shader_path)?;
+
+        self.create_checked_background_renderer(config, &self.frag_shader_path)
+    }
+
+    /// Create background renderer with validation errors reported as `Err`
+    fn create_checked_background_renderer(
+        &self,
+        config: &NeedleConfig,
+        frag_shader_path: &Path,
+    ) -> Result<ShaderRenderer> {
+        let device = self.state.device();
+
+        // Catch pipeline errors (e.g. mismatching uniform) instead of panicking
+        device.push_error_scope(wgpu::ErrorFilter::Validation);
+        let renderer = Self::create_background_renderer(
+            config,
+            &self.state,
+            &self.vert_shader_path,
+            frag_shader_path,
+        );
+        if let Some(e) = pollster::block_on(device.pop_error_scope()) {
+            bail!("{e}");
+        }
+
+        renderer
+    }
+
+    /// Create/Remove outline and shadow layers to match config
+    fn sync_text_effects(&mut self, config: &NeedleConfig, app_config: &AppConfig) -> Result<()> {
+        let effects = [
+            (
+                &mut self.time_effect,
+                &app_config.effects.time,
+                config.time.font.clone(),
+            ),
+            (
+                &mut self.fps_effect,
+                &app_config.effects.fps,
+                app_config.fonts.fps.clone(),
+            ),
+        ];
+
+        for (effect, effect_config, font) in effects {
+            let specs = layer_specs(effect_config);
+
+            // Layers going back to the default font are created again like the text renderers
+            if effect.layers.len() != specs.len() || (effect.font != font && font.is_none()) {
+                effect.layers = specs
+                    .into_iter()
+                    .map(|spec| {
+                        Ok(EffectLayer {
+                            renderer: Self::create_text_renderer(
+                                &self.window,
+                                config,
+                                &self.state,
+                                font.clone(),
+                            )?,
+                            region: TextRegion::new(&self.window.inner_size()),
+                            spec,
+                        })
+                    })
+                    .collect::<Result<_>>()?;
+                effect.font = font;
+                continue;
+            }
+
+            effect
+                .layers
+                .iter_mut()
+                .zip(specs)
+                .for_each(|(layer, spec)| layer.spec = spec);
+            if let Some(new_font) = font.as_ref().filter(|_| effect.font != font) {
+                for layer in effect.layers.iter_mut() {
+                    if let Err(e) = layer.renderer.set_font(new_font) {
+                        log::error!("{e}");
+                    }
+                }
+                effect.font = font;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Update outline/shadow layers drawn behind text
+    #[allow(clippy::too_many_arguments)]
+    fn update_text_effect(
+        effect: &mut TextEffect,
+        state: &State,
+        text: &str,
+        position: Position,
+        offset: [f32; 2],
+        size: &PhysicalSize<u32>,
+        margin: f32,
+        set_config: impl Fn(&mut TextRenderer, [u8; 3]),
+    ) -> NeedleErr<()> {
+        for layer in effect.layers.iter_mut() {
+            let layer_offset = [
+                offset[0] + layer.spec.offset[0],
+                offset[1] + layer.spec.offset[1],
+            ];
+            let region = TextRegion::with_offset(position, layer_offset, size);
+
+            Self::layout_text(&mut layer.renderer, &mut layer.region, region);
+            layer.renderer.set_text(text);
+            set_config(&mut layer.renderer, layer.spec.color);
+            layer.renderer.update(state);
+            layer.renderer.prepare(margin, state)?;
+        }
+
+        Ok(())
+    }
+
+    /// Lay out text in new region if region has changed
+    fn layout_text(renderer: &mut TextRenderer, current: &mut TextRegion, region: TextRegion) {
+        if *current != region {
+            renderer.resize(&region.size());
+            *current = region;
+        }
+    }
+
+    /// Region for subtitle placed under/above the clock.
+    /// `metrics` are the metrics of time and subtitle text.
+    fn subtitle_region(
+        config: &NeedleConfig,
+        subtitle: &SubtitleConfig,
+        time_offset: [f32; 2],
+        size: &PhysicalSize<u32>,
+        metrics: [&TextMetrics; 2],
+    ) -> TextRegion {
+        let position = config.time.config.position;
+        let (_, anchor_y) = anchor(position);
+        let (_, time_height) = metrics[0].text_size("0", config.time.config.scale);
+        let (_, subtitle_height) = metrics[1].text_size(&subtitle.text(), subtitle.scale);
+        let offset = match subtitle.placement {
+            SubtitlePlacement::Below => {
+                time_height * (1.0 - anchor_y) + subtitle_height * anchor_y + subtitle.spacing
+            }
+            SubtitlePlacement::Above => {
+                -(time_height * anchor_y + subtitle_height * (1.0 - anchor_y) + subtitle.spacing)
+            }
+        };
+
+        TextRegion::with_offset(position, [time_offset[0], time_offset[1] + offset], size)
+    }
+
+    /// Size of render surface
+    fn surface_size(&self) -> PhysicalSize<u32> {
+        let surface_config = self.state.surface_config();
+
+        PhysicalSize::new(surface_config.width, surface_config.height)
+    }
+
+    /// Update Imgui UI for needle
+    fn update_imgui(
+        &mut self,
+        config: &mut NeedleConfig,
+        app_config: &mut AppConfig,
+    ) -> NeedleErr<()> {
+        // Imgui Tags
+        const NEEDLE_IMGUI_WINDOW_TITLE: &str = "Needle Settings";
+        const NEEDLE_IMGUI_WINDOW_SIZE: [f32; 2] = [800.0, 600.0];
+        const NEEDLE_IMGUI_SETTINGS: &str = "Settings";
+        const NEEDLE_IMGUI_SAVE: &str = "Save";
+        const NEEDLE_IMGUI_PROFILES: &str = "Profiles";
+        const NEEDLE_IMGUI_UNSAVED: &str = "* Unsaved changes";
+        const NEEDLE_IMGUI_REVERT: &str = "Revert to saved";
+        const NEEDLE_IMGUI_RESET: &str = "Reset to defaults";
+        const NEEDLE_IMGUI_LANGUAGE: &str = "Language";
+        const NEEDLE_IMGUI_WINDOW: &str = "Window";
+        const NEEDLE_IMGUI_REMEMBER_WINDOW: &str = "Remember window size and position";
+        const OVERLAY_ALWAYS_ON_TOP: &str = "Always on top";
+        const OVERLAY_DECORATIONS: &str = "Show title bar";
+        const OVERLAY_SKIP_TASKBAR: &str = "Hide from taskbar";
+        const OVERLAY_CLICK_THROUGH: &str = "Click-through (while menu is closed)";
+        const OVERLAY_INFO: &str =
+            "Press \"INSERT\" on the focused window or run \"needle --send menu\" to open the menu again";
+        #[cfg(target_os = "linux")]
+        const OVERLAY_SKIP_TASKBAR_INFO: &str = "Hiding from taskbar applies on next launch";
+        const QUIT_WINDOW_TITLE: &str = "Quit Needle";
+        const QUIT_WINDOW_SIZE: [f32; 2] = [400.0, 120.0];
+        const QUIT_INFO: &str = "There are unsaved changes.";
+        const QUIT_SAVE: &str = "Save and Quit";
+        const QUIT_DISCARD: &str = "Quit without Saving";
+        const QUIT_CANCEL: &str = "Cancel";
+        const SHADER_ERROR_WINDOW_TITLE: &str = "Shader Error";
+        const SHADER_ERROR_WINDOW_SIZE: [f32; 2] = [600.0, 300.0];
+        const SHADER_ERROR_INFO: &str =
+            "Shader failed to load. Fix the shader and save it to reload.";
+        //  - Background
+        const BACKGROUND_STYLE: &str = "Style";
+        const BACKGROUND_SHADER: &str = "Custom Shader";
+        const BACKGROUND_SHADER_ENABLE: &str = "Use custom shader";
+        const BACKGROUND_SHADER_PATH: &str = "WGSL File";
+        const BACKGROUND_SHADER_LOAD: &str = "Load";
+        const BACKGROUND_SHADER_INFO: &str =
+            "Relative paths are resolved from the config directory";
+        //  - Clock Timer
+        const CLOCK_TIMER_FONT: &str = "Font";
+        const FONT_SHOW_ALL: &str = "List all system fonts";
+        const FONT_FILES_INFO: &str =
+            "Font files in \"fonts\" of the config directory are listed too";
+        const CLOCK_TIMER_FONT_SCALE: &str = "Font Scale";
+        const CLOCK_TIMER_POSITION: &str = "Clock Position";
+        const TEXT_EFFECTS: &str = "Outline/Shadow";
+        const TEXT_OFFSET_INFO: &str = "Drag text with mouse to move it while this menu is open";
+        const CLOCK_TIMER_MODE: &str = "Mode:";
+        const CLOCK_TIMER_FORMAT_MODE: &str = "Format Mode";
+        const CLOCK_TIMER_CUSTOM_FORMAT: &str = "Custom Format";
+        const CLOCK_TIMER_CUSTOM_FORMAT_CLOCK: &str = "Clock Format";
+        const CLOCK_TIMER_CUSTOM_FORMAT_TIMER: &str = "Timer Format";
+        const CLOCK_TIMER_CUSTOM_FORMAT_CLOCK_INFO: &str =
+            "strftime pattern (e.g. \"%a %d %b %H:%M:%S\")";
+        const CLOCK_TIMER_CUSTOM_FORMAT_TIMER_INFO: &str =
+            "Tokens: {d} {h} {hh} {m} {mm} {s} {ss} {f} {ff} {ms}";
+        const CLOCK_TIMER_CLOCK_MODE: &str = "Clock Mode";
         const CLOCK_TIMER_CLOCK_MODE_INFO: &str = "Press \"SPACE\" to start/stop timer";
-        const CLOCK_TIMER_CLOCK_MODE_DURATION: &str = "Countdown Duration";
+        const CLOCK_TIMER_PRECISION: &str = "Timer Precision";
+        const CLOCK_TIMER_SUBTITLE: &str = "Subtitle";
+        const CLOCK_TIMER_FINISH: &str = "On Finish";
+        const CLOCK_TIMER_POMODORO: &str = "Pomodoro";
+        const CLOCK_TIMER_PLAYLIST: &str = "Playlist";
         //  - FPS
         const FPS_VISUALIZATION: &str = "Toggle FPS visualization";
-        const FPS_FONT_COLOR: &str = "Font Color:";
+        const FPS_FONT: &str = "Font##fps";
         const FPS_POSITION: &str = "FPS Position";
 
-        self.imgui_state.setup(&self.window, |ui, settings_mode| {
-            let window = ui.window(NEEDLE_IMGUI_WINDOW_TITLE);
+        let size = self.surface_size();
+
+        let mut reload_shader = false;
+        let mut switch_profile = None;
+        let mut apply_config = false;
+        let mut query_fonts = false;
+        let mut apply_overlay = false;
+
+        self.menu_visible = false;
+        self.imgui_wants_keyboard = false;
+        let Some(imgui_state) = self.imgui_state.as_mut() else {
+            return Ok(());
+        };
+        let result = imgui_state.setup(&self.window, |ui, settings_mode| {
+            self.menu_visible = true;
+            self.imgui_wants_mouse = ui.io().want_capture_mouse;
+            self.imgui_wants_keyboard = ui.io().want_text_input;
+
+            let window = ui.window(tr(NEEDLE_IMGUI_WINDOW_TITLE));
             let mut mode: i8 = i8::from(*settings_mode);
             let mut save_result: NeedleErr<()> = Ok(());
 
@@ -234,7 +1414,11 @@ impl<'a> NeedleBase<'a> {
                 .build(|| {
                     // --- Mode Selection ---
                     if ui
-                        .slider_config(NEEDLE_IMGUI_SETTINGS, ImguiMode::BACKGROUND, ImguiMode::MAX)
+                        .slider_config(
+                            tr(NEEDLE_IMGUI_SETTINGS),
+                            ImguiMode::BACKGROUND,
+                            ImguiMode::MAX,
+                        )
                         .display_format(format!("{settings_mode}"))
                         .build(&mut mode)
                     {
@@ -244,22 +1428,61 @@ impl<'a> NeedleBase<'a> {
 
                     match settings_mode {
                         ImguiMode::Background => {
-                            let mut background_color = config
-                                .background_color
-                                .iter()
-                                .map(|val| (*val * 255.0) as u8)
-                                .collect::<Vec<_>>();
+                            let mut background_color: [f32; 4] =
+                                std::array::from_fn(|i| config.background_color[i]);
 
-                            ui.text(BACKGROUND_COLOR);
-                            Self::background_color()
-                                .iter()
-                                .enumerate()
-                                .for_each(|(i, tag)| {
-                                    if ui.slider(tag, 0, 255, &mut background_color[i]) {
-                                        config.background_color[i] =
-                                            background_color[i] as f32 / 255.0;
-                                    };
-                                });
+                            if Self::color_edit(
+                                ui,
+                                Self::background_color(),
+                                &mut background_color,
+                                true,
+                            ) {
+                                config
+                                    .background_color
+                                    .iter_mut()
+                                    .zip(background_color)
+                                    .for_each(|(color, value)| *color = value);
+                            }
+                            ui.separator();
+
+                            // --- Style ---
+                            let background = &mut app_config.background;
+                            let mut style = i8::from(background.style);
+
+                            if ui
+                                .slider_config(
+                                    tr(BACKGROUND_STYLE),
+                                    BackgroundStyle::SOLID,
+                                    BackgroundStyle::MAX,
+                                )
+                                .display_format(tr(&background.style.to_string()))
+                                .build(&mut style)
+                            {
+                                background.style = style.into();
+                            }
+                            match background.style {
+                                BackgroundStyle::Solid => (),
+                                BackgroundStyle::LinearGradient
+                                | BackgroundStyle::RadialGradient => {
+                                    Self::gradient_settings(ui, background)
+                                }
+                                BackgroundStyle::Panel => Self::panel_settings(ui, background),
+                            }
+
+                            // --- Custom shader ---
+                            if ui.collapsing_header(tr(BACKGROUND_SHADER), TreeNodeFlags::empty()) {
+                                let shader = &mut background.shader;
+
+                                if ui.checkbox(tr(BACKGROUND_SHADER_ENABLE), &mut shader.enable) {
+                                    reload_shader = true;
+                                }
+                                ui.input_text(tr(BACKGROUND_SHADER_PATH), &mut shader.path)
+                                    .build();
+                                ui.text(tr(BACKGROUND_SHADER_INFO));
+                                if shader.enable && ui.button(tr(BACKGROUND_SHADER_LOAD)) {
+                                    reload_shader = true;
+                                }
+                            }
                         }
                         ImguiMode::ClockTimer => {
                             // --- Font selection ---
@@ -279,7 +1502,7 @@ impl<'a> NeedleBase<'a> {
                                 .unwrap_or(0);
 
                             if ui.list_box(
-                                CLOCK_TIMER_FONT,
+                                tr(CLOCK_TIMER_FONT),
                                 &mut clock_font,
                                 font_names.as_ref(),
                                 Self::CLOCK_TIMER_FONT_ROWS as i32,
@@ -292,70 +1515,106 @@ impl<'a> NeedleBase<'a> {
                                     log::error!("{e}");
                                 }
                             }
+                            if ui.checkbox(tr(FONT_SHOW_ALL), &mut app_config.fonts.show_all) {
+                                query_fonts = true;
+                            }
+                            ui.text(tr(FONT_FILES_INFO));
                             ui.separator();
 
                             // --- Font color ---
-                            ui.text(CLOCK_TIMER_FONT_COLOR);
-                            Self::clock_font_color()
-                                .iter()
-                                .enumerate()
-                                .for_each(|(i, tag)| {
-                                    ui.slider(tag, 0, 255, &mut config.time.config.color[i]);
-                                });
+                            Self::text_color_edit(
+                                ui,
+                                Self::clock_font_color(),
+                                &mut config.time.config.color,
+                                Some(&mut app_config.text_alpha.time),
+                            );
 
                             // --- Font scale ---
                             let mut clock_scale = (config.time.config.scale * 100.0) as u8;
-                            if ui.slider(CLOCK_TIMER_FONT_SCALE, 1, u8::MAX, &mut clock_scale) {
+                            if ui.slider(tr(CLOCK_TIMER_FONT_SCALE), 1, u8::MAX, &mut clock_scale) {
                                 config.time.config.scale = clock_scale as f32 / 50.0;
                             }
                             ui.separator();
 
                             // --- Clock position ---
                             let mut clock_position = config.time.config.position.into();
+                            let positions = Self::clock_position().map(tr);
+                            let positions = positions
+                                .iter()
+                                .map(|position| position.as_ref())
+                                .collect::<Vec<_>>();
 
                             if ui.list_box(
-                                CLOCK_TIMER_POSITION,
+                                tr(CLOCK_TIMER_POSITION),
                                 &mut clock_position,
-                                &Self::clock_position(),
+                                &positions,
                                 Self::CLOCK_TIMER_POSITION_COUNT as i32,
                             ) {
-                                let position = Position::from(clock_position);
-
-                                if config.fps.config.position != position {
-                                    config.time.config.position = position;
-                                }
+                                config.time.config.position = Position::from(clock_position);
                             }
+                            Self::text_offset(
+                                ui,
+                                &mut app_config.layout.time,
+                                &size,
+                                Self::clock_offset(),
+                            );
+                            ui.text(tr(TEXT_OFFSET_INFO));
                             ui.separator();
                             // --- Format Mode ---
                             let mut view_mode: i8 = config.time.format.into();
 
-                            ui.text(CLOCK_TIMER_MODE);
+                            ui.text(tr(CLOCK_TIMER_MODE));
                             if ui
                                 .slider_config(
-                                    CLOCK_TIMER_FORMAT_MODE,
+                                    tr(CLOCK_TIMER_FORMAT_MODE),
                                     TimeFormat::HOUR_MIN_SEC,
                                     TimeFormat::MAX,
                                 )
-                                .display_format(format!("{}", config.time.format))
+                                .display_format(tr(&config.time.format.to_string()))
                                 .build(&mut view_mode)
                             {
                                 config.time.format = view_mode.into();
                                 self.clock_info.set_format(config.time.format);
                             }
+
+                            // --- Custom Format ---
+                            let custom_format = &mut app_config.custom_format;
+
+                            ui.checkbox(tr(CLOCK_TIMER_CUSTOM_FORMAT), &mut custom_format.enable);
+                            if custom_format.enable {
+                                ui.input_text(
+                                    tr(CLOCK_TIMER_CUSTOM_FORMAT_CLOCK),
+                                    &mut custom_format.clock,
+                                )
+                                .build();
+                                ui.text(tr(CLOCK_TIMER_CUSTOM_FORMAT_CLOCK_INFO));
+                                Self::format_preview(ui, format_clock(&custom_format.clock));
+                                ui.input_text(
+                                    tr(CLOCK_TIMER_CUSTOM_FORMAT_TIMER),
+                                    &mut custom_format.timer,
+                                )
+                                .build();
+                                ui.text(tr(CLOCK_TIMER_CUSTOM_FORMAT_TIMER_INFO));
+                                Self::format_preview(
+                                    ui,
+                                    format_timer(
+                                        &custom_format.timer,
+                                        self.clock_info.timer_value(),
+                                    ),
+                                );
+                            }
                             ui.separator();
 
                             // --- Clock Mode ---
                             let mut clock_mode: i8 = self.clock_info.mode().into();
-                            let mut countdown_duration =
-                                if let OpMode::CountDownTimer(duration) = self.clock_info.mode() {
-                                    duration
-                                } else {
-                                    Duration::new(0, 0)
-                                };
 
                             if ui
-                                .slider_config(CLOCK_TIMER_CLOCK_MODE, OpMode::CLOCK, OpMode::MAX)
-                                .display_format(format!("{}", self.clock_info.mode()))
+                                .slider_config(
+                                    tr(CLOCK_TIMER_CLOCK_MODE),
+                                    OpMode::CLOCK,
+                                    OpMode::MAX,
+                                )
+                                .display_format(tr(&self.clock_info.mode().to_string()))
                                 .build(&mut clock_mode)
                             {
                                 match clock_mode.into() {
@@ -364,107 +1623,1024 @@ impl<'a> NeedleBase<'a> {
                                     }
                                     OpMode::CountUpTimer => {
                                         self.clock_info.set_mode(OpMode::CountUpTimer);
-                                        ui.text(CLOCK_TIMER_CLOCK_MODE_INFO);
+                                        ui.text(tr(CLOCK_TIMER_CLOCK_MODE_INFO));
                                     }
                                     OpMode::CountDownTimer(_) => {
-                                        self.clock_info
-                                            .set_mode(OpMode::CountDownTimer(countdown_duration));
+                                        self.clock_info.set_mode(OpMode::CountDownTimer(
+                                            app_config.timer.countdown.unwrap_or_default(),
+                                        ));
                                     }
                                 }
                             }
 
                             match self.clock_info.mode() {
-                                OpMode::CountDownTimer(_) => {
-                                    let mut countdown_sec = 0;
-
-                                    ui.text(CLOCK_TIMER_CLOCK_MODE_INFO);
-                                    if ui
-                                        .input_int(
-                                            CLOCK_TIMER_CLOCK_MODE_DURATION,
-                                            &mut countdown_sec,
-                                        )
-                                        .build()
-                                    {
-                                        countdown_duration = Duration::new(countdown_sec as u64, 0)
+                                OpMode::CountDownTimer(duration) => {
+                                    ui.text(tr(CLOCK_TIMER_CLOCK_MODE_INFO));
+                                    if let Some(duration) = Self::countdown_duration(
+                                        ui,
+                                        duration,
+                                        &mut self.countdown_input,
+                                    ) {
+                                        self.clock_info.set_mode(OpMode::CountDownTimer(duration));
+                                        app_config.timer.countdown = Some(duration);
+                                    }
+                                    if ui.collapsing_header(
+                                        tr(CLOCK_TIMER_FINISH),
+                                        TreeNodeFlags::empty(),
+                                    ) {
+                                        Self::finish_settings(ui, &mut app_config.timer.finish);
                                     }
-                                    self.clock_info
-                                        .set_mode(OpMode::CountDownTimer(countdown_duration));
                                 }
                                 OpMode::CountUpTimer => {
-                                    ui.text(CLOCK_TIMER_CLOCK_MODE_INFO);
+                                    ui.text(tr(CLOCK_TIMER_CLOCK_MODE_INFO));
                                 }
                                 _ => (),
                             }
+
+                            // --- Timer Precision ---
+                            let timer = &mut app_config.timer;
+                            let mut precision: i8 = timer.precision.into();
+
+                            if ui
+                                .slider_config(
+                                    tr(CLOCK_TIMER_PRECISION),
+                                    TimerPrecision::SECONDS,
+                                    TimerPrecision::MAX,
+                                )
+                                .display_format(tr(&timer.precision.to_string()))
+                                .build(&mut precision)
+                            {
+                                timer.precision = precision.into();
+                            }
+                            ui.separator();
+
+                            // --- Pomodoro ---
+                            if ui
+                                .collapsing_header(tr(CLOCK_TIMER_POMODORO), TreeNodeFlags::empty())
+                            {
+                                if Self::pomodoro_settings(ui, &mut app_config.pomodoro) {
+                                    app_config.playlist.enable = false;
+                                }
+                            }
+
+                            // --- Playlist ---
+                            if ui
+                                .collapsing_header(tr(CLOCK_TIMER_PLAYLIST), TreeNodeFlags::empty())
+                            {
+                                if Self::playlist_settings(ui, &mut app_config.playlist) {
+                                    app_config.pomodoro.enable = false;
+                                }
+                            }
+
+                            // --- Subtitle ---
+                            if ui
+                                .collapsing_header(tr(CLOCK_TIMER_SUBTITLE), TreeNodeFlags::empty())
+                            {
+                                Self::subtitle_settings(
+                                    ui,
+                                    &mut app_config.subtitle,
+                                    &mut self.time_renderer,
+                                    &mut self.subtitle_renderer,
+                                );
+                            }
+
+                            // --- Outline/Shadow ---
+                            if ui.collapsing_header(tr(TEXT_EFFECTS), TreeNodeFlags::empty()) {
+                                let _id = ui.push_id("time");
+
+                                Self::text_effect_settings(ui, &mut app_config.effects.time);
+                            }
                         }
                         ImguiMode::Fps => {
                             // --- Enable/Disable FPS visualization ---
                             let mut fps_enable = if config.fps.enable { 1 } else { 0 };
 
                             if ui
-                                .slider_config(FPS_VISUALIZATION, 0, 1)
-                                .display_format(Self::fps_enable(config.fps.enable))
+                                .slider_config(tr(FPS_VISUALIZATION), 0, 1)
+                                .display_format(tr(Self::fps_enable(config.fps.enable)))
                                 .build(&mut fps_enable)
                             {
                                 config.fps.enable = fps_enable % 2 == 1;
                             }
                             ui.separator();
 
-                            // FPS font color
-                            ui.text(FPS_FONT_COLOR);
-                            Self::fps_font_color()
+                            // --- Font selection ---
+                            let fonts = self.fps_renderer.fonts_mut();
+                            let font_names = fonts.font_names().unwrap_or([].into());
+                            let font_names = font_names
                                 .iter()
-                                .enumerate()
-                                .for_each(|(i, tag)| {
-                                    ui.slider(tag, 0, u8::MAX, &mut config.fps.config.color[i]);
-                                });
+                                .map(|font| font.as_str())
+                                .collect::<Vec<_>>();
+                            let mut fps_font = font_names
+                                .iter()
+                                .position(|font| Some(*font) == app_config.fonts.fps.as_deref())
+                                .unwrap_or(0) as i32;
+
+                            if ui.list_box(
+                                tr(FPS_FONT),
+                                &mut fps_font,
+                                font_names.as_ref(),
+                                Self::CLOCK_TIMER_FONT_ROWS as i32,
+                            ) {
+                                let font = &fonts.available_fonts()[fps_font as usize];
+
+                                app_config.fonts.fps = Some(font.font.to_string());
+                                if let Err(e) = self.fps_renderer.set_font(&font.font) {
+                                    log::error!("{font:?}");
+                                    log::error!("{e}");
+                                }
+                            }
+                            if ui.checkbox(tr(FONT_SHOW_ALL), &mut app_config.fonts.show_all) {
+                                query_fonts = true;
+                            }
+                            ui.separator();
+
+                            // FPS font color
+                            Self::text_color_edit(
+                                ui,
+                                Self::fps_font_color(),
+                                &mut config.fps.config.color,
+                                Some(&mut app_config.text_alpha.fps),
+                            );
                             ui.separator();
 
                             // --- FPS text position ---
                             let mut fps_position: i32 = config.fps.config.position.into();
+                            let positions = Self::clock_position().map(tr);
+                            let positions = positions
+                                .iter()
+                                .map(|position| position.as_ref())
+                                .collect::<Vec<_>>();
 
                             if ui.list_box(
-                                FPS_POSITION,
+                                tr(FPS_POSITION),
                                 &mut fps_position,
-                                &Self::fps_position(),
-                                Self::FPS_POSITION_COUNT as i32,
+                                &positions,
+                                Self::CLOCK_TIMER_POSITION_COUNT as i32,
                             ) {
-                                const OFFSET: i32 = Position::TOP_LEFT as i32;
-                                const TOP_LEFT: i32 = Position::TOP_LEFT as i32 - OFFSET;
-                                const TOP_RIGHT: i32 = Position::TOP_RIGHT as i32 - OFFSET;
-                                const BOTTOM_LEFT: i32 = Position::BOTTOM_LEFT as i32 - OFFSET;
-                                const BOTTOM_RIGHT: i32 = Position::BOTTOM_RIGHT as i32 - OFFSET;
-
-                                let position = match fps_position {
-                                    TOP_LEFT => Position::TopLeft,
-                                    TOP_RIGHT => Position::TopRight,
-                                    BOTTOM_LEFT => Position::BottomLeft,
-                                    BOTTOM_RIGHT => Position::BottomRight,
-                                    _ => config.fps.config.position,
-                                };
-
-                                if config.time.config.position != position {
-                                    config.fps.config.position = position;
-                                }
+                                config.fps.config.position = Position::from(fps_position);
+                            }
+                            Self::text_offset(
+                                ui,
+                                &mut app_config.layout.fps,
+                                &size,
+                                Self::fps_offset(),
+                            );
+                            ui.text(tr(TEXT_OFFSET_INFO));
+                            ui.separator();
+
+                            // --- Outline/Shadow ---
+                            if ui.collapsing_header(tr(TEXT_EFFECTS), TreeNodeFlags::empty()) {
+                                let _id = ui.push_id("fps");
+
+                                Self::text_effect_settings(ui, &mut app_config.effects.fps);
                             }
                         }
                     }
+                    ui.separator();
+                    if ui.button(tr(NEEDLE_IMGUI_RESET)) {
+                        Self::reset_section(*settings_mode, config, app_config);
+                        apply_config = true;
+                    }
+
+                    // --- Profiles ---
+                    ui.separator();
+                    if ui.collapsing_header(tr(NEEDLE_IMGUI_PROFILES), TreeNodeFlags::empty()) {
+                        switch_profile = Self::profile_settings(
+                            ui,
+                            config,
+                            app_config,
+                            &mut self.profile_name_input,
+                            &mut self.profile_error,
+                        );
+                    }
+
+                    // --- Window ---
+                    if ui.collapsing_header(tr(NEEDLE_IMGUI_WINDOW), TreeNodeFlags::empty()) {
+                        let overlay = &mut app_config.overlay;
+
+                        ui.checkbox(
+                            tr(NEEDLE_IMGUI_REMEMBER_WINDOW),
+                            &mut app_config.window.remember,
+                        );
+                        apply_overlay |=
+                            ui.checkbox(tr(OVERLAY_ALWAYS_ON_TOP), &mut overlay.always_on_top);
+                        apply_overlay |=
+                            ui.checkbox(tr(OVERLAY_DECORATIONS), &mut overlay.decorations);
+                        apply_overlay |=
+                            ui.checkbox(tr(OVERLAY_SKIP_TASKBAR), &mut overlay.skip_taskbar);
+                        // Window type of X11 cannot be changed after the window is mapped
+                        #[cfg(target_os = "linux")]
+                        ui.text(tr(OVERLAY_SKIP_TASKBAR_INFO));
+                        ui.checkbox(tr(OVERLAY_CLICK_THROUGH), &mut overlay.click_through);
+                        if overlay.click_through {
+                            ui.text(tr(OVERLAY_INFO));
+                        }
+                    }
+
+                    // --- Language ---
+                    let mut language = i8::from(app_config.language);
+
+                    if ui
+                        .slider_config(
+                            tr(NEEDLE_IMGUI_LANGUAGE),
+                            Language::AUTO,
+                            Language::MENU_MAX,
+                        )
+                        .display_format(tr(&app_config.language.to_string()))
+                        .build(&mut language)
+                    {
+                        app_config.language = language.into();
+                        set_language(app_config.language);
+                    }
 
                     // Save current settings
                     ui.separator();
                     Self::save().iter().for_each(|tag| {
-                        ui.text(tag);
+                        ui.text(tr(tag));
                     });
-                    if ui.button(NEEDLE_IMGUI_SAVE) {
-                        save_result = config.save_config();
+                    if ui.button(tr(NEEDLE_IMGUI_SAVE)) {
+                        if app_config.window.remember {
+                            app_config.window = Self::window_geometry(&self.window);
+                        }
+                        app_config.window.drop_unremembered();
+                        save_result = app_config.save(config).map_err(|e| {
+                            log::error!("{e}");
+
+                            NeedleError::InvalidPath
+                        });
+                        self.unsaved_checked = None;
+                    }
+                    if !matches!(
+                        self.unsaved_checked,
+                        Some(checked) if checked.elapsed() < Self::UNSAVED_CHECK_INTERVAL
+                    ) {
+                        self.unsaved = app_config.is_dirty(config);
+                        self.unsaved_checked = Some(Instant::now());
+                    }
+                    if self.unsaved {
+                        ui.same_line();
+                        ui.text_colored(Self::DIRTY_TEXT_COLOR, tr(NEEDLE_IMGUI_UNSAVED));
+                        ui.same_line();
+                        if ui.button(tr(NEEDLE_IMGUI_REVERT)) {
+                            if let Err(e) = app_config.revert(config) {
+                                log::error!("{e}");
+                            }
+                            apply_config = true;
+                            self.unsaved_checked = None;
+                        }
                     }
 
                     // Description
                     ui.separator();
-                    Self::description().iter().for_each(|tag| ui.text(tag));
+                    Self::description().iter().for_each(|tag| ui.text(tr(tag)));
                 });
 
+            // Shader errors are kept until the shader loads successfully
+            if let Some(e) = self.shader_error.as_ref() {
+                ui.window(tr(SHADER_ERROR_WINDOW_TITLE))
+                    .size(SHADER_ERROR_WINDOW_SIZE, Condition::FirstUseEver)
+                    .build(|| {
+                        ui.text(tr(SHADER_ERROR_INFO));
+                        ui.separator();
+                        ui.text_colored(Self::ERROR_TEXT_COLOR, e);
+                    });
+            }
+
+            // Confirmation for quitting with unsaved changes
+            if self.quit_requested {
+                ui.window(tr(QUIT_WINDOW_TITLE))
+                    .size(QUIT_WINDOW_SIZE, Condition::Always)
+                    .build(|| {
+                        ui.text(tr(QUIT_INFO));
+                        if ui.button(tr(QUIT_SAVE)) {
+                            save_result = app_config.save(config).map_err(|e| {
+                                log::error!("{e}");
+
+                                NeedleError::InvalidPath
+                            });
+                            self.quit = save_result.is_ok();
+                        }
+                        ui.same_line();
+                        if ui.button(tr(QUIT_DISCARD)) {
+                            self.quit = true;
+                        }
+                        ui.same_line();
+                        if ui.button(tr(QUIT_CANCEL)) {
+                            self.quit_requested = false;
+                        }
+                    });
+            }
+
             save_result
-        })
+        });
+
+        if reload_shader {
+            self.load_background_shader(config, &app_config.background.shader, false);
+        }
+        if let Some(name) = switch_profile {
+            self.switch_profile(&name, config, app_config);
+        }
+        if apply_config {
+            self.apply_config(config, app_config);
+        } else if query_fonts {
+            for renderer in [&mut self.time_renderer, &mut self.fps_renderer] {
+                if let Err(e) = Self::query_fonts(renderer, &app_config.fonts) {
+                    log::error!("{e}");
+                }
+            }
+        }
+        if apply_overlay {
+            self.apply_overlay(&app_config.overlay);
+        }
+
+        result
+    }
+
+    /// Hour/Minute/Second fields for duration.
+    /// Returns new duration if it has been modified.
+    fn duration_fields(ui: &imgui::Ui, duration: Duration) -> Option<Duration> {
+        const DURATION_FIELD_WIDTH: f32 = 100.0;
+
+        let (hours, minutes, seconds) = split_duration(&duration);
+        let mut fields = [hours as i64, minutes as i64, seconds as i64];
+        let mut new_duration = None;
+
+        for (i, tag) in Self::countdown_duration_fields().iter().enumerate() {
+            let mut field = fields[i] as i32;
+
+            if i > 0 {
+                ui.same_line();
+            }
+            ui.set_next_item_width(DURATION_FIELD_WIDTH);
+            if ui.input_int(tr(tag), &mut field).build() {
+                fields[i] = field as i64;
+
+                // Overflowing/underflowing fields carry over to the next unit
+                let secs = fields[0] * 3600 + fields[1] * 60 + fields[2];
+
+                new_duration = Some(Duration::from_secs(secs.max(0) as u64));
+            }
+        }
+
+        new_duration
+    }
+
+    /// Countdown duration editor with hour/minute/second fields, presets and text entry.
+    /// Returns new duration if it has been modified.
+    fn countdown_duration(
+        ui: &imgui::Ui,
+        duration: Duration,
+        input: &mut String,
+    ) -> Option<Duration> {
+        const COUNTDOWN_DURATION: &str = "Countdown Duration";
+        const COUNTDOWN_DURATION_INPUT: &str = "Duration##countdown";
+        const COUNTDOWN_DURATION_INPUT_HINT: &str = "1h30m, 90:00, 1:30:00, 45s";
+
+        ui.text(tr(COUNTDOWN_DURATION));
+
+        // --- Hour/Minute/Second fields ---
+        let mut new_duration = Self::duration_fields(ui, duration);
+
+        // --- Presets ---
+        for (i, (tag, preset)) in Self::countdown_presets().iter().enumerate() {
+            if i > 0 {
+                ui.same_line();
+            }
+            if ui.button(tag) {
+                new_duration = Some(new_duration.unwrap_or(duration) + *preset);
+            }
+        }
+
+        // --- Text entry ---
+        if ui
+            .input_text(tr(COUNTDOWN_DURATION_INPUT), input)
+            .hint(COUNTDOWN_DURATION_INPUT_HINT)
+            .enter_returns_true(true)
+            .build()
+        {
+            if let Ok(duration) = parse_duration(input) {
+                new_duration = Some(duration);
+                input.clear();
+            }
+        }
+        if !input.is_empty() {
+            if let Err(e) = parse_duration(input) {
+                ui.text_colored(Self::ERROR_TEXT_COLOR, e.to_string());
+            }
+        }
+
+        new_duration
+    }
+
+    /// Settings for actions when countdown timer reaches zero
+    fn finish_settings(ui: &imgui::Ui, finish: &mut FinishConfig) {
+        const FINISH_MESSAGE: &str = "Message##finish";
+        const FINISH_MESSAGE_HINT: &str = "Shown in place of the time";
+        const FINISH_BLINK: &str = "Blink";
+        const FINISH_LIMIT: &str = "Show for limited time";
+        const FINISH_START: &str = "Start Next Timer";
+        const FINISH_COMMAND: &str = "Command##finish";
+        const FINISH_COMMAND_INFO: &str =
+            "Timer is described in NEEDLE_TIMER_* environment variables";
+        const FINISH_MARKER_FILE: &str = "Marker File";
+        const FINISH_MARKER_FILE_INFO: &str =
+            "Relative paths are resolved from the config directory";
+        const DEFAULT_TEXT_COLOR: [u8; 3] = [255, 64, 64];
+        const DEFAULT_BACKGROUND_COLOR: [f32; 4] = [0.0, 0.0, 0.0, 1.0];
+        const DEFAULT_DURATION: Duration = Duration::from_secs(10);
+
+        let tags = Self::finish_colors();
+        let mut custom_color = finish.color.is_some();
+        let mut custom_background = finish.background_color.is_some();
+        let mut limit = finish.duration.is_some();
+        let mut start: i8 = finish.start.into();
+
+        ui.input_text(tr(FINISH_MESSAGE), &mut finish.message)
+            .hint(tr(FINISH_MESSAGE_HINT))
+            .build();
+        if ui.checkbox(tr(tags[0]), &mut custom_color) {
+            finish.color = custom_color.then_some(DEFAULT_TEXT_COLOR);
+        }
+        if let Some(color) = finish.color.as_mut() {
+            Self::text_color_edit(ui, [tags[1], tags[2]], color, None);
+        }
+        if ui.checkbox(tr(tags[3]), &mut custom_background) {
+            finish.background_color = custom_background.then_some(DEFAULT_BACKGROUND_COLOR);
+        }
+        if let Some(color) = finish.background_color.as_mut() {
+            Self::color_edit(ui, [tags[4], tags[5]], color, true);
+        }
+        ui.checkbox(tr(FINISH_BLINK), &mut finish.blink);
+        if ui.checkbox(tr(FINISH_LIMIT), &mut limit) {
+            finish.duration = limit.then_some(DEFAULT_DURATION);
+        }
+        if let Some(duration) = finish.duration {
+            let _id = ui.push_id("finish_duration");
+
+            if let Some(duration) = Self::duration_fields(ui, duration) {
+                finish.duration = Some(duration);
+            }
+        }
+
+        // --- Next timer ---
+        if ui
+            .slider_config(tr(FINISH_START), NextTimer::NONE, NextTimer::MAX)
+            .display_format(tr(&finish.start.to_string()))
+            .build(&mut start)
+        {
+            finish.start = start.into();
+        }
+        if let NextTimer::Countdown(duration) = finish.start {
+            let _id = ui.push_id("finish_start");
+
+            if let Some(duration) = Self::duration_fields(ui, duration) {
+                finish.start = NextTimer::Countdown(duration);
+            }
+        }
+
+        // --- Command/Marker file ---
+        ui.input_text(tr(FINISH_COMMAND), &mut finish.command)
+            .build();
+        ui.text(tr(FINISH_COMMAND_INFO));
+        ui.input_text(tr(FINISH_MARKER_FILE), &mut finish.marker_file)
+            .build();
+        ui.text(tr(FINISH_MARKER_FILE_INFO));
+    }
+
+    /// Settings for Pomodoro mode.
+    /// Returns true if Pomodoro mode has been turned on.
+    fn pomodoro_settings(ui: &imgui::Ui, pomodoro: &mut PomodoroConfig) -> bool {
+        const POMODORO_ENABLE: &str = "Pomodoro mode";
+        const POMODORO_INFO: &str = "Press \"S\" to skip to next phase";
+        const POMODORO_ROUNDS: &str = "Rounds before long break";
+        const POMODORO_AUTO_START: &str = "Start next phase automatically";
+        const POMODORO_MAX_MINUTES: i32 = 24 * 60;
+        const DEFAULT_TEXT_COLOR: [u8; 3] = [255, 255, 255];
+        const DEFAULT_BACKGROUND_COLOR: [f32; 4] = [0.0, 0.0, 0.0, 1.0];
+
+        let enabled = ui.checkbox(tr(POMODORO_ENABLE), &mut pomodoro.enable) && pomodoro.enable;
+
+        ui.text(tr(POMODORO_INFO));
+
+        let mut rounds = pomodoro.rounds as i32;
+
+        if ui.input_int(tr(POMODORO_ROUNDS), &mut rounds).build() {
+            pomodoro.rounds = rounds.max(1) as u32;
+        }
+        ui.checkbox(tr(POMODORO_AUTO_START), &mut pomodoro.auto_start);
+
+        for phase in PomodoroPhase::ALL {
+            let tags = Self::pomodoro_phase(phase);
+            let settings = pomodoro.phase_mut(phase);
+            let mut minutes = (settings.duration.as_secs() / 60) as i32;
+            let mut custom_color = settings.color.is_some();
+            let mut custom_background = settings.background_color.is_some();
+
+            ui.separator();
+            ui.text(tr(&phase.to_string()));
+            if ui.input_int(tr(tags[0]), &mut minutes).build() {
+                settings.duration =
+                    Duration::from_secs(minutes.clamp(1, POMODORO_MAX_MINUTES) as u64 * 60);
+            }
+            if ui.checkbox(tr(tags[1]), &mut custom_color) {
+                settings.color = custom_color.then_some(DEFAULT_TEXT_COLOR);
+            }
+            if let Some(color) = settings.color.as_mut() {
+                Self::text_color_edit(ui, [tags[2], tags[3]], color, None);
+            }
+            if ui.checkbox(tr(tags[4]), &mut custom_background) {
+                settings.background_color = custom_background.then_some(DEFAULT_BACKGROUND_COLOR);
+            }
+            if let Some(color) = settings.background_color.as_mut() {
+                Self::color_edit(ui, [tags[5], tags[6]], color, true);
+            }
+        }
+
+        enabled
+    }
+
+    /// Settings for timer playlist.
+    /// Returns true if playlist has been turned on.
+    fn playlist_settings(ui: &imgui::Ui, playlist: &mut PlaylistConfig) -> bool {
+        const PLAYLIST_ENABLE: &str = "Run playlist";
+        const PLAYLIST_INFO: &str = "Press \"S\" to skip to next segment";
+        const PLAYLIST_AUTO_ADVANCE: &str = "Start next segment automatically";
+        const PLAYLIST_SEGMENT: &str = "Segment";
+        const PLAYLIST_SEGMENT_NAME: &str = "Name##segment";
+        const PLAYLIST_SEGMENT_MODE: &str = "Mode##segment";
+        const PLAYLIST_SEGMENT_END: &str = "End at duration";
+        const PLAYLIST_SEGMENT_ADD: &str = "Add Segment";
+        const PLAYLIST_SEGMENT_REMOVE: &str = "Remove Segment";
+        const PLAYLIST_SEGMENT_UP: &str = "Move Up";
+        const DEFAULT_TEXT_COLOR: [u8; 3] = [255, 255, 255];
+        const DEFAULT_BACKGROUND_COLOR: [f32; 4] = [0.0, 0.0, 0.0, 1.0];
+
+        let enabled = ui.checkbox(tr(PLAYLIST_ENABLE), &mut playlist.enable) && playlist.enable;
+        let tags = Self::playlist_segment();
+        let mut remove = None;
+        let mut move_up = None;
+
+        ui.text(tr(PLAYLIST_INFO));
+        ui.checkbox(tr(PLAYLIST_AUTO_ADVANCE), &mut playlist.auto_advance);
+        for (i, segment) in playlist.segments.iter_mut().enumerate() {
+            let _id = ui.push_id_usize(i);
+            let mut mode: i8 = segment.mode.into();
+            let mut end = segment.duration.is_some();
+            let mut custom_color = segment.color.is_some();
+            let mut custom_background = segment.background_color.is_some();
+
+            ui.separator();
+            ui.text(format!("{} {}:", tr(PLAYLIST_SEGMENT), i + 1));
+            ui.input_text(tr(PLAYLIST_SEGMENT_NAME), &mut segment.name)
+                .build();
+            if ui
+                .slider_config(
+                    tr(PLAYLIST_SEGMENT_MODE),
+                    PlaylistSegmentMode::COUNTDOWN,
+                    PlaylistSegmentMode::MAX,
+                )
+                .display_format(tr(&segment.mode.to_string()))
+                .build(&mut mode)
+            {
+                segment.mode = mode.into();
+                if segment.mode == PlaylistSegmentMode::Countdown {
+                    segment
+                        .duration
+                        .get_or_insert(PlaylistSegment::DEFAULT_DURATION);
+                }
+            }
+            if segment.mode == PlaylistSegmentMode::CountUp
+                && ui.checkbox(tr(PLAYLIST_SEGMENT_END), &mut end)
+            {
+                segment.duration = end.then_some(PlaylistSegment::DEFAULT_DURATION);
+            }
+            if let Some(duration) = segment.duration {
+                if let Some(duration) = Self::duration_fields(ui, duration) {
+                    segment.duration = Some(duration);
+                }
+            }
+            if ui.checkbox(tr(tags[0]), &mut custom_color) {
+                segment.color = custom_color.then_some(DEFAULT_TEXT_COLOR);
+            }
+            if let Some(color) = segment.color.as_mut() {
+                Self::text_color_edit(ui, [tags[1], tags[2]], color, None);
+            }
+            if ui.checkbox(tr(tags[3]), &mut custom_background) {
+                segment.background_color = custom_background.then_some(DEFAULT_BACKGROUND_COLOR);
+            }
+            if let Some(color) = segment.background_color.as_mut() {
+                Self::color_edit(ui, [tags[4], tags[5]], color, true);
+            }
+            if i > 0 && ui.button(tr(PLAYLIST_SEGMENT_UP)) {
+                move_up = Some(i);
+            }
+            if i > 0 {
+                ui.same_line();
+            }
+            if ui.button(tr(PLAYLIST_SEGMENT_REMOVE)) {
+                remove = Some(i);
+            }
+        }
+        if let Some(i) = move_up {
+            playlist.segments.swap(i - 1, i);
+        }
+        if let Some(i) = remove {
+            playlist.segments.remove(i);
+        }
+        ui.separator();
+        if ui.button(tr(PLAYLIST_SEGMENT_ADD)) {
+            playlist.segments.push(PlaylistSegment::default());
+        }
+
+        enabled
+    }
+
+    /// Settings for subtitle
+    fn subtitle_settings(
+        ui: &imgui::Ui,
+        subtitle: &mut SubtitleConfig,
+        time_renderer: &mut TextRenderer,
+        subtitle_renderer: &mut TextRenderer,
+    ) {
+        const SUBTITLE_ENABLE: &str = "Show subtitle";
+        const SUBTITLE_CONTENT: &str = "Content";
+        const SUBTITLE_DATE_FORMAT: &str = "Date Format";
+        const SUBTITLE_LABEL: &str = "Label";
+        const SUBTITLE_PLACEMENT: &str = "Placement";
+        const SUBTITLE_FONT: &str = "Font##subtitle";
+        const SUBTITLE_FONT_SCALE: &str = "Font Scale##subtitle";
+        const SUBTITLE_SPACING: &str = "Spacing";
+
+        ui.checkbox(tr(SUBTITLE_ENABLE), &mut subtitle.enable);
+        if !subtitle.enable {
+            return;
+        }
+
+        // --- Content ---
+        let mut content: i8 = subtitle.content.into();
+
+        if ui
+            .slider_config(
+                tr(SUBTITLE_CONTENT),
+                SubtitleContent::DATE,
+                SubtitleContent::MAX,
+            )
+            .display_format(tr(&subtitle.content.to_string()))
+            .build(&mut content)
+        {
+            subtitle.content = content.into();
+        }
+        if subtitle.content != SubtitleContent::Label {
+            ui.input_text(tr(SUBTITLE_DATE_FORMAT), &mut subtitle.date_format)
+                .build();
+            Self::format_preview(ui, format_clock(&subtitle.date_format));
+        }
+        if subtitle.content != SubtitleContent::Date {
+            ui.input_text(tr(SUBTITLE_LABEL), &mut subtitle.label)
+                .build();
+        }
+
+        // --- Placement ---
+        let mut placement: i8 = subtitle.placement.into();
+
+        if ui
+            .slider_config(
+                tr(SUBTITLE_PLACEMENT),
+                SubtitlePlacement::BELOW,
+                SubtitlePlacement::MAX,
+            )
+            .display_format(tr(&subtitle.placement.to_string()))
+            .build(&mut placement)
+        {
+            subtitle.placement = placement.into();
+        }
+        ui.slider(tr(SUBTITLE_SPACING), -50.0, 200.0, &mut subtitle.spacing);
+        ui.separator();
+
+        // --- Font selection ---
+        let fonts = time_renderer.fonts_mut();
+        let font_names = fonts.font_names().unwrap_or([].into());
+        let font_names = font_names
+            .iter()
+            .map(|font| font.as_str())
+            .collect::<Vec<_>>();
+        let mut subtitle_font = font_names
+            .iter()
+            .position(|font| Some(*font) == subtitle.font.as_deref())
+            .unwrap_or(0) as i32;
+
+        if ui.list_box(
+            tr(SUBTITLE_FONT),
+            &mut subtitle_font,
+            font_names.as_ref(),
+            Self::CLOCK_TIMER_FONT_ROWS as i32,
+        ) {
+            let font = &fonts.available_fonts()[subtitle_font as usize];
+
+            subtitle.font = Some(font.font.to_string());
+            if let Err(e) = subtitle_renderer.set_font(&font.font) {
+                log::error!("{font:?}");
+                log::error!("{e}");
+            }
+        }
+
+        // --- Font color ---
+        Self::text_color_edit(ui, Self::subtitle_font_color(), &mut subtitle.color, None);
+
+        // --- Font scale ---
+        ui.slider(tr(SUBTITLE_FONT_SCALE), 0.1, 5.0, &mut subtitle.scale);
+    }
+
+    /// Reset settings shown on the page of `mode` to defaults
+    fn reset_section(mode: ImguiMode, config: &mut NeedleConfig, app_config: &mut AppConfig) {
+        let default = NeedleConfig::default();
+        let app_default = AppConfig::default();
+
+        match mode {
+            ImguiMode::Background => {
+                config.background_color = default.background_color;
+                app_config.background = app_default.background;
+            }
+            ImguiMode::ClockTimer => {
+                config.time = default.time;
+                app_config.timer = app_default.timer;
+                app_config.custom_format = app_default.custom_format;
+                app_config.subtitle = app_default.subtitle;
+                app_config.pomodoro = app_default.pomodoro;
+                app_config.playlist = app_default.playlist;
+                app_config.layout.time = app_default.layout.time;
+                app_config.effects.time = app_default.effects.time;
+                app_config.text_alpha.time = app_default.text_alpha.time;
+            }
+            ImguiMode::Fps => {
+                config.fps = default.fps;
+                app_config.layout.fps = app_default.layout.fps;
+                app_config.effects.fps = app_default.effects.fps;
+                app_config.text_alpha.fps = app_default.text_alpha.fps;
+                app_config.fonts.fps = app_default.fonts.fps;
+            }
+        }
+    }
+
+    /// Profile selection and management.
+    /// Returns name of profile to switch to if another profile has been selected.
+    fn profile_settings(
+        ui: &imgui::Ui,
+        config: &NeedleConfig,
+        app_config: &mut AppConfig,
+        name: &mut String,
+        error: &mut Option<String>,
+    ) -> Option<String> {
+        const PROFILE: &str = "Profile";
+        const PROFILE_NONE: &str = "No profiles saved yet";
+        const PROFILE_NAME: &str = "Name";
+        const PROFILE_SAVE: &str = "Save Profile";
+        const PROFILE_SAVE_AS: &str = "Save As";
+        const PROFILE_DUPLICATE: &str = "Duplicate As";
+        const PROFILE_DELETE: &str = "Delete Profile";
+        const PROFILE_INFO: &str = "Press \"F2\" to switch to next profile";
+
+        let names = app_config
+            .profiles
+            .names()
+            .into_iter()
+            .map(str::to_string)
+            .collect::<Vec<_>>();
+        let active = app_config.profiles.active.clone();
+        let mut selected = active
+            .as_ref()
+            .and_then(|active| names.iter().position(|name| name == active))
+            .unwrap_or(0);
+        let mut switch_to = None;
+
+        if names.is_empty() {
+            ui.text(tr(PROFILE_NONE));
+        } else if ui.combo_simple_string(tr(PROFILE), &mut selected, &names)
+            && active.as_ref() != Some(&names[selected])
+        {
+            switch_to = Some(names[selected].clone());
+        }
+        ui.input_text(tr(PROFILE_NAME), name).build();
+
+        let save = active.is_some() && ui.button(tr(PROFILE_SAVE));
+        let save_as = ui.button(tr(PROFILE_SAVE_AS));
+        let duplicate = active.is_some() && ui.button(tr(PROFILE_DUPLICATE));
+        let delete = active.is_some() && ui.button(tr(PROFILE_DELETE));
+        let result = match active.as_deref() {
+            Some(active) if save => Some(app_config.save_profile(active, config)),
+            _ if save_as => Some(app_config.save_profile(name, config)),
+            Some(active) if duplicate => Some(
+                app_config
+                    .profiles
+                    .duplicate(active, name)
+                    .and_then(|_| app_config.profiles.save()),
+            ),
+            Some(active) if delete => Some(
+                app_config
+                    .profiles
+                    .remove(active)
+                    .and_then(|_| app_config.profiles.save()),
+            ),
+            _ => None,
+        };
+
+        if let Some(result) = result {
+            *error = result.err().map(|e| {
+                log::error!("{e}");
+
+                e.to_string()
+            });
+        }
+        if let Some(e) = error.as_ref() {
+            ui.text_colored(Self::ERROR_TEXT_COLOR, e);
+        }
+        ui.text(tr(PROFILE_INFO));
+
+        switch_to
+    }
+
+    /// Settings for gradient background
+    fn gradient_settings(ui: &imgui::Ui, background: &mut BackgroundConfig) {
+        const GRADIENT_ANGLE: &str = "Angle";
+        const GRADIENT_STOP: &str = "Stop";
+        const GRADIENT_STOP_POSITION: &str = "Position";
+        const GRADIENT_STOP_ADD: &str = "Add Stop";
+        const GRADIENT_STOP_REMOVE: &str = "Remove Stop";
+
+        let gradient = &mut background.gradient;
+        let removable = gradient.stops.len() > GradientConfig::MIN_STOPS;
+        let mut remove = None;
+
+        if background.style == BackgroundStyle::LinearGradient {
+            ui.slider(tr(GRADIENT_ANGLE), 0.0, 360.0, &mut gradient.angle);
+        }
+        for (i, stop) in gradient.stops.iter_mut().enumerate() {
+            let _id = ui.push_id_usize(i);
+
+            ui.separator();
+            ui.text(format!("{} {}:", tr(GRADIENT_STOP), i + 1));
+            ui.slider(tr(GRADIENT_STOP_POSITION), 0.0, 1.0, &mut stop.position);
+            Self::color_edit(ui, Self::background_color(), &mut stop.color, true);
+            if removable && ui.button(tr(GRADIENT_STOP_REMOVE)) {
+                remove = Some(i);
+            }
+        }
+        if let Some(i) = remove {
+            gradient.stops.remove(i);
+        }
+        ui.separator();
+        if gradient.stops.len() < GradientConfig::MAX_STOPS && ui.button(tr(GRADIENT_STOP_ADD)) {
+            let stop = gradient.stops.last().copied().unwrap_or(GradientStop {
+                position: 1.0,
+                color: [0.0, 0.0, 0.0, 1.0],
+            });
+
+            gradient.stops.push(stop);
+        }
+    }
+
+    /// Settings for rounded panel background
+    fn panel_settings(ui: &imgui::Ui, background: &mut BackgroundConfig) {
+        const PANEL_WIDTH: &str = "Width (%)";
+        const PANEL_HEIGHT: &str = "Height (%)";
+        const PANEL_RADIUS: &str = "Corner Radius";
+        const PANEL_BORDER_WIDTH: &str = "Border Width";
+
+        let panel = &mut background.panel;
+
+        ui.slider(tr(PANEL_WIDTH), 0.0, 100.0, &mut panel.size[0]);
+        ui.slider(tr(PANEL_HEIGHT), 0.0, 100.0, &mut panel.size[1]);
+        ui.slider(tr(PANEL_RADIUS), 0.0, 200.0, &mut panel.radius);
+        ui.slider(tr(PANEL_BORDER_WIDTH), 0.0, 20.0, &mut panel.border_width);
+        Self::color_edit(ui, Self::border_color(), &mut panel.border_color, true);
+    }
+
+    /// Color picker with hex (`#RRGGBBAA`) entry for color stored as 0.0 - 1.0.
+    /// Returns true if the color has changed.
+    fn color_edit(
+        ui: &imgui::Ui,
+        tags: [&str; NeedleBase::COLOR_EDIT_TAG_COUNT],
+        color: &mut [f32; 4],
+        alpha: bool,
+    ) -> bool {
+        let [color_tag, hex_tag] = tags.map(tr);
+        let mut changed = ui
+            .color_edit4_config(color_tag, color)
+            .alpha(alpha)
+            .alpha_bar(alpha)
+            .build();
+        let mut hex = to_hex(color, alpha);
+
+        if ui
+            .input_text(hex_tag, &mut hex)
+            .enter_returns_true(true)
+            .build()
+        {
+            match parse_hex(&hex) {
+                Ok(rgba) => {
+                    let channels = if alpha { 4 } else { 3 };
+
+                    color[..channels]
+                        .iter_mut()
+                        .zip(rgba)
+                        .for_each(|(color, value)| *color = value as f32 / 255.0);
+                    changed = true;
+                }
+                Err(e) => log::error!("{e}"),
+            }
+        }
+
+        changed
+    }
+
+    /// Color picker for text colors stored as `[u8; 3]`, with alpha stored separately
+    fn text_color_edit(
+        ui: &imgui::Ui,
+        tags: [&str; NeedleBase::COLOR_EDIT_TAG_COUNT],
+        color: &mut [u8; 3],
+        mut alpha: Option<&mut f32>,
+    ) {
+        const ALPHA_INFO: &str =
+            "Alpha blends the text toward the background color (text is not made translucent)";
+
+        let mut rgba = [
+            color[0] as f32 / 255.0,
+            color[1] as f32 / 255.0,
+            color[2] as f32 / 255.0,
+            alpha.as_deref().copied().unwrap_or(1.0),
+        ];
+
+        if Self::color_edit(ui, tags, &mut rgba, alpha.is_some()) {
+            *color = std::array::from_fn(|i| (rgba[i] * 255.0).round() as u8);
+            if let Some(alpha) = alpha.as_deref_mut() {
+                *alpha = rgba[3];
+            }
+        }
+        if alpha.is_some() {
+            ui.text(tr(ALPHA_INFO));
+        }
+    }
+
+    /// Settings for outline and drop shadow of text
+    fn text_effect_settings(ui: &imgui::Ui, effect: &mut TextEffectConfig) {
+        const OUTLINE: &str = "Outline";
+        const OUTLINE_WIDTH: &str = "Outline Width";
+        const SHADOW: &str = "Drop Shadow";
+        const SHADOW_OFFSET: &str = "Shadow Offset";
+
+        // --- Outline ---
+        ui.checkbox(tr(OUTLINE), &mut effect.outline.enable);
+        if effect.outline.enable {
+            ui.slider(tr(OUTLINE_WIDTH), 0.5, 8.0, &mut effect.outline.width);
+            Self::text_color_edit(ui, Self::outline_color(), &mut effect.outline.color, None);
+        }
+        ui.separator();
+
+        // --- Drop shadow ---
+        ui.checkbox(tr(SHADOW), &mut effect.shadow.enable);
+        if effect.shadow.enable {
+            ui.input_float2(tr(SHADOW_OFFSET), &mut effect.shadow.offset)
+                .build();
+            Self::text_color_edit(ui, Self::shadow_color(), &mut effect.shadow.color, None);
+        }
+    }
+
+    /// Offset editor of text
+    fn text_offset(
+        ui: &imgui::Ui,
+        offset: &mut TextOffset,
+        size: &PhysicalSize<u32>,
+        tags: [&str; NeedleBase::TEXT_OFFSET_TAG_COUNT],
+    ) {
+        let [offset_tag, unit_tag, reset_tag] = tags.map(tr);
+        let mut xy = [offset.x, offset.y];
+        let mut unit: i8 = offset.unit.into();
+
+        if ui.input_float2(offset_tag, &mut xy).build() {
+            [offset.x, offset.y] = xy;
+        }
+        if ui
+            .slider_config(unit_tag, OffsetUnit::PIXEL, OffsetUnit::MAX)
+            .display_format(tr(&offset.unit.to_string()))
+            .build(&mut unit)
+        {
+            offset.set_unit(unit.into(), size);
+        }
+        if ui.button(reset_tag) {
+            offset.x = 0.0;
+            offset.y = 0.0;
+        }
+    }
+
+    /// Live preview of custom format
+    fn format_preview(ui: &imgui::Ui, preview: Result<String>) {
+        const FORMAT_PREVIEW: &str = "Preview:";
+
+        match preview {
+            Ok(preview) => ui.text(format!("{} {preview}", tr(FORMAT_PREVIEW))),
+            Err(e) => ui.text_colored(Self::ERROR_TEXT_COLOR, e.to_string()),
+        }
     }
 
     /// Render single frame for needle
@@ -495,26 +2671,70 @@ impl<'a> NeedleBase<'a> {
             });
 
             self.background_renderer.render(&mut render_pass)?;
+            Self::render_text_effect(&mut self.time_effect, &mut render_pass)?;
+            self.time_region.set_viewport(&mut render_pass, 0.0);
             self.time_renderer.render(&mut render_pass)?;
+            self.subtitle_region.set_viewport(&mut render_pass, 0.0);
+            self.subtitle_renderer.render(&mut render_pass)?;
+            Self::render_text_effect(&mut self.fps_effect, &mut render_pass)?;
+            self.fps_region.set_viewport(&mut render_pass, 0.0);
             self.fps_renderer.render(&mut render_pass)?;
 
             Ok(())
         })
     }
 
+    /// Render outline/shadow layers behind text
+    fn render_text_effect(
+        effect: &mut TextEffect,
+        render_pass: &mut wgpu::RenderPass,
+    ) -> NeedleErr<()> {
+        for layer in effect.layers.iter_mut() {
+            layer.region.set_viewport(render_pass, layer.spec.depth);
+            layer.renderer.render(render_pass)?;
+        }
+
+        Ok(())
+    }
+
+    /// List monospace fonts, or all system fonts if `show_all` is set
+    fn query_fonts(renderer: &mut TextRenderer, fonts: &FontsConfig) -> NeedleErr<()> {
+        let font_type = if fonts.show_all {
+            None
+        } else {
+            Some(FontTypes::Monospace)
+        };
+
+        renderer.fonts_mut().query_fonts(font_type)
+    }
+
+    /// Create text renderer with `font` (default font if `None`).
+    /// Used for outline/shadow layers and to restore the default font.
+    fn create_text_renderer(
+        window: &Window,
+        config: &NeedleConfig,
+        state: &State,
+        font: Option<String>,
+    ) -> Result<TextRenderer> {
+        Ok(TextRenderer::new(
+            state,
+            &config.time.config,
+            font,
+            &window.inner_size(),
+            window.scale_factor(),
+            state.surface_config().format,
+            Some(Texture::default_depth_stencil()),
+        )?)
+    }
+
     #[inline]
-    const fn background_color<'color>() -> [&'color str; NeedleBase::BACKGROUND_COLOR_COUNT] {
-        [
-            "red (background)",
-            "green (background)",
-            "blue (background)",
-            "alpha (background)",
-        ]
+    const fn background_color<'color>() -> [&'color str; NeedleBase::COLOR_EDIT_TAG_COUNT] {
+        ["Color##background", "Hex##background"]
     }
 
     #[inline]
-    const fn clock_font_color<'color>() -> [&'color str; NeedleBase::CLOCK_TIMER_FONT_COLOR_COUNT] {
-        ["red (text)", "green (text)", "blue (text)"]
+    const fn clock_font_color<'color>() -> [&'color str; NeedleBase::COLOR_EDIT_TAG_COUNT] {
+        ["Font Color##text", "Hex##text"]
     }
 
     #[inline]
@@ -533,6 +2753,106 @@ impl<'a> NeedleBase<'a> {
         ]
     }
 
+    #[inline]
+    const fn countdown_duration_fields<'field>(
+    ) -> [&'field str; NeedleBase::COUNTDOWN_DURATION_FIELD_COUNT] {
+        [
+            "Hours##countdown",
+            "Minutes##countdown",
+            "Seconds##countdown",
+        ]
+    }
+
+    #[inline]
+    const fn countdown_presets<'preset>(
+    ) -> [(&'preset str, Duration); NeedleBase::COUNTDOWN_PRESET_COUNT] {
+        [
+            ("+1m", Duration::from_secs(60)),
+            ("+5m", Duration::from_secs(5 * 60)),
+            ("+15m", Duration::from_secs(15 * 60)),
+        ]
+    }
+
+    #[inline]
+    const fn finish_colors<'color>() -> [&'color str; NeedleBase::FINISH_COLOR_TAG_COUNT] {
+        [
+            "Custom text color##finish",
+            "Text Color##finish",
+            "Hex##finish_text",
+            "Custom background color##finish",
+            "Background Color##finish",
+            "Hex##finish_background",
+        ]
+    }
+
+    #[inline]
+    const fn playlist_segment<'segment>() -> [&'segment str; NeedleBase::PLAYLIST_SEGMENT_TAG_COUNT]
+    {
+        [
+            "Custom text color##segment",
+            "Text Color##segment",
+            "Hex##segment_text",
+            "Custom background color##segment",
+            "Background Color##segment",
+            "Hex##segment_background",
+        ]
+    }
+
+    #[inline]
+    const fn pomodoro_phase<'phase>(
+        phase: PomodoroPhase,
+    ) -> [&'phase str; NeedleBase::POMODORO_PHASE_TAG_COUNT] {
+        match phase {
+            PomodoroPhase::Work => [
+                "Duration (minutes)##work",
+                "Custom text color##work",
+                "Text Color##work",
+                "Hex##work_text",
+                "Custom background color##work",
+                "Background Color##work",
+                "Hex##work_background",
+            ],
+            PomodoroPhase::ShortBreak => [
+                "Duration (minutes)##short_break",
+                "Custom text color##short_break",
+                "Text Color##short_break",
+                "Hex##short_break_text",
+                "Custom background color##short_break",
+                "Background Color##short_break",
+                "Hex##short_break_background",
+            ],
+            PomodoroPhase::LongBreak => [
+                "Duration (minutes)##long_break",
+                "Custom text color##long_break",
+                "Text Color##long_break",
+                "Hex##long_break_text",
+                "Custom background color##long_break",
+                "Background Color##long_break",
+                "Hex##long_break_background",
+            ],
+        }
+    }
+
+    #[inline]
+    const fn subtitle_font_color<'color>() -> [&'color str; NeedleBase::COLOR_EDIT_TAG_COUNT] {
+        ["Font Color##subtitle", "Hex##subtitle"]
+    }
+
+    #[inline]
+    const fn border_color<'color>() -> [&'color str; NeedleBase::COLOR_EDIT_TAG_COUNT] {
+        ["Border Color##border", "Hex##border"]
+    }
+
+    #[inline]
+    const fn outline_color<'color>() -> [&'color str; NeedleBase::COLOR_EDIT_TAG_COUNT] {
+        ["Outline Color##outline", "Hex##outline"]
+    }
+
+    #[inline]
+    const fn shadow_color<'color>() -> [&'color str; NeedleBase::COLOR_EDIT_TAG_COUNT] {
+        ["Shadow Color##shadow", "Hex##shadow"]
+    }
+
     #[inline]
     const fn fps_enable<'enable>(enable: bool) -> &'enable str {
         if enable {
@@ -543,13 +2863,18 @@ impl<'a> NeedleBase<'a> {
     }
 
     #[inline]
-    const fn fps_font_color<'color>() -> [&'color str; NeedleBase::FPS_FONT_COLOR_COUNT] {
-        ["red (fps)", "green (fps)", "blue (fps)"]
+    const fn fps_font_color<'color>() -> [&'color str; NeedleBase::COLOR_EDIT_TAG_COUNT] {
+        ["Font Color##fps", "Hex##fps"]
     }
 
     #[inline]
-    const fn fps_position<'position>() -> [&'position str; NeedleBase::FPS_POSITION_COUNT] {
-        ["Top Left", "Top Right", "Bottom Left", "Bottom Right"]
+    const fn clock_offset<'offset>() -> [&'offset str; NeedleBase::TEXT_OFFSET_TAG_COUNT] {
+        ["Offset##time", "Offset Unit##time", "Reset Offset##time"]
+    }
+
+    #[inline]
+    const fn fps_offset<'offset>() -> [&'offset str; NeedleBase::TEXT_OFFSET_TAG_COUNT] {
+        ["Offset##fps", "Offset Unit##fps", "Reset Offset##fps"]
     }
 
     #[inline]
@@ -570,24 +2895,61 @@ impl<'a> NeedleBase<'a> {
     fn create_renderers(
         window: Arc<Window>,
         config: Rc<RefCell<NeedleConfig>>,
+        app_config: &AppConfig,
         state: &State,
-        vert_shader_path: &str,
-        frag_shader_path: &str,
-    ) -> Result<(ShaderRenderer, TextRenderer, TextRenderer)> {
+    ) -> Result<(TextRenderer, TextRenderer, TextRenderer)> {
         let window_size = window.inner_size();
         let window_scale_factor = window.scale_factor();
         let depth_stencil_state = Texture::default_depth_stencil();
-        let (background_vertices, indices) = Vertex::indexed_rectangle(
-            [1.0, 1.0],
-            [0.0, 0.0],
-            0.1,
-            &config.borrow().background_color,
-        );
+        let mut time_renderer = TextRenderer::new(
+            state,
+            &config.borrow().time.config,
+            config.borrow().time.font.clone(),
+            &window_size,
+            window_scale_factor,
+            state.surface_config().format,
+            Some(depth_stencil_state.clone()),
+        )?;
+        let mut fps_renderer = TextRenderer::new(
+            state,
+            &config.borrow().fps.config,
+            app_config.fonts.fps.clone(),
+            &window_size,
+            window_scale_factor,
+            state.surface_config().format,
+            Some(depth_stencil_state.clone()),
+        )?;
+        let subtitle_renderer = TextRenderer::new(
+            state,
+            &config.borrow().time.config,
+            app_config.subtitle.font.clone(),
+            &window_size,
+            window_scale_factor,
+            state.surface_config().format,
+            Some(depth_stencil_state.clone()),
+        )?;
+
+        Self::query_fonts(&mut time_renderer, &app_config.fonts)?;
+        Self::query_fonts(&mut fps_renderer, &app_config.fonts)?;
+
+        Ok((time_renderer, fps_renderer, subtitle_renderer))
+    }
+
+    /// Create renderer for background with shaders at given paths
+    fn create_background_renderer(
+        config: &NeedleConfig,
+        state: &State,
+        vert_shader_path: &Path,
+        frag_shader_path: &Path,
+    ) -> Result<ShaderRenderer> {
+        let (background_vertices, indices) =
+            Vertex::indexed_rectangle([1.0, 1.0], [0.0, 0.0], 0.1, &config.background_color);
+        // UBO is visible to the fragment stage, where `fs_main.wgsl` reads it
         let ubo_bind_group_layout = BindGroupLayout::builder().add_ubo().build(
             state.device(),
             NeedleLabel::BindGroupLayout("Background UBO"),
         );
-        let background_ubo = Ubo::new::<glm::Vec4>(
+        let background_ubo = Ubo::new::<BackgroundUbo>(
             state.device(),
             NeedleLabel::Buffer("Background UBO"),
             &ubo_bind_group_layout,
@@ -601,43 +2963,17 @@ impl<'a> NeedleBase<'a> {
             0,
             Some(&indices),
         );
-        let background_renderer = {
-            let desc = ShaderRendererDescriptor {
-                vert_shader_path: NeedleConfig::config_path(false, Some(vert_shader_path))?,
-                frag_shader_path: NeedleConfig::config_path(false, Some(frag_shader_path))?,
-                buffer: background_buffer,
-                ubo: Some(background_ubo),
-                vertex_buffer_layout: Vertex::buffer_layout(),
-                bind_group_layouts: vec![ubo_bind_group_layout],
-                depth_stencil: Some(depth_stencil_state.clone()),
-                label: Some("Background"),
-            };
-
-            ShaderRenderer::new(state, &desc)?
+        let desc = ShaderRendererDescriptor {
+            vert_shader_path: vert_shader_path.to_path_buf(),
+            frag_shader_path: frag_shader_path.to_path_buf(),
+            buffer: background_buffer,
+            ubo: Some(background_ubo),
+            vertex_buffer_layout: Vertex::buffer_layout(),
+            bind_group_layouts: vec![ubo_bind_group_layout],
+            depth_stencil: Some(Texture::default_depth_stencil()),
+            label: Some("Background"),
         };
-        let mut time_renderer = TextRenderer::new(
-            state,
-            &config.borrow().time.config,
-            config.borrow().time.font.clone(),
-            &window_size,
-            window_scale_factor,
-            state.surface_config().format,
-            Some(depth_stencil_state.clone()),
-        )?;
-        let fps_renderer = TextRenderer::new(
-            state,
-            &config.borrow().fps.config,
-            None,
-            &window_size,
-            window_scale_factor,
-            state.surface_config().format,
-            Some(depth_stencil_state.clone()),
-        )?;
-
-        time_renderer
-            .fonts_mut()
-            .query_fonts(Some(FontTypes::Monospace))?;
 
-        Ok((background_renderer, time_renderer, fps_renderer))
+        Ok(ShaderRenderer::new(state, &desc)?)
     }
 }
@@ -0,0 +1,129 @@
+// Copyright 2025 Kensuke Saito
+// SPDX-License-Identifier: MIT
+
+use crate::{
+    format::{format_clock, format_timer},
+    CustomFormatConfig, TimerPrecision,
+};
+use needle_core::{OpMode, Time, TimeFormat};
+use std::time::{Duration, Instant};
+
+/// Appearance of clock changed by timer modes (e.g. phase of Pomodoro mode)
+#[derive(Debug, Default, Clone)]
+pub struct ClockStyle {
+    /// Text shown before the time
+    pub label: Option<String>,
+    /// Text shown in place of the time
+    pub text: Option<String>,
+    pub color: Option<[u8; 3]>,
+    pub background_color: Option<[f32; 4]>,
+}
+
+/// Clock/Timer of needle.
+/// Keeps track of the timer with monotonic clock alongside `Time`,
+/// so the timer value is available for custom formats.
+pub struct Clock {
+    time: Time,
+    started: Option<Instant>,
+    elapsed: Duration,
+}
+
+impl Clock {
+    pub fn new(format: TimeFormat) -> Self {
+        Self {
+            time: Time::new(format),
+            started: None,
+            elapsed: Duration::ZERO,
+        }
+    }
+
+    pub fn mode(&self) -> OpMode {
+        self.time.mode()
+    }
+
+    /// Set clock mode.
+    /// Timer is reset on mode change.
+    pub fn set_mode(&mut self, mode: OpMode) {
+        self.time.set_mode(mode);
+        self.started = None;
+        self.elapsed = Duration::ZERO;
+    }
+
+    pub fn set_format(&mut self, format: TimeFormat) {
+        self.time.set_format(format);
+    }
+
+    /// Start/Stop timer
+    pub fn toggle_timer(&mut self) {
+        self.time.toggle_timer();
+        match self.started.take() {
+            Some(started) => self.elapsed += started.elapsed(),
+            None => self.started = Some(Instant::now()),
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.started.is_some()
+    }
+
+    /// Time elapsed since timer has been started (excluding paused time)
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+            + self
+                .started
+                .map(|started| started.elapsed())
+                .unwrap_or_default()
+    }
+
+    /// Value of timer.
+    /// Remaining time for countdown timer, and elapsed time for count up timer.
+    pub fn timer_value(&self) -> Duration {
+        match self.mode() {
+            OpMode::CountDownTimer(duration) => duration.saturating_sub(self.elapsed()),
+            OpMode::Clock | OpMode::CountUpTimer => self.elapsed(),
+        }
+    }
+
+    /// Progress of countdown timer (0.0 - 1.0).
+    /// Always 0.0 for clock and count up timer.
+    pub fn progress(&self) -> f32 {
+        match self.mode() {
+            OpMode::CountDownTimer(duration) if !duration.is_zero() => {
+                (self.elapsed().as_secs_f32() / duration.as_secs_f32()).min(1.0)
+            }
+            _ => 0.0,
+        }
+    }
+
+    /// Current time/timer value formatted for display.
+    /// Timers with sub-second `precision` are formatted from the monotonic timer value.
+    /// Falls back to `TimeFormat` if custom format is disabled or invalid.
+    pub fn current_time(
+        &self,
+        custom_format: &CustomFormatConfig,
+        precision: TimerPrecision,
+    ) -> String {
+        if custom_format.enable {
+            let time = match self.mode() {
+                OpMode::Clock => format_clock(&custom_format.clock),
+                OpMode::CountDownTimer(_) | OpMode::CountUpTimer => {
+                    format_timer(&custom_format.timer, self.timer_value())
+                }
+            };
+
+            if let Ok(time) = time {
+                return time;
+            }
+        }
+
+        if let (OpMode::CountDownTimer(_) | OpMode::CountUpTimer, Some(format)) =
+            (self.mode(), precision.format())
+        {
+            if let Ok(time) = format_timer(format, self.timer_value()) {
+                return time;
+            }
+        }
+
+        self.time.current_time()
+    }
+}
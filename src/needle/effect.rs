@@ -0,0 +1,73 @@
+// Copyright 2025 Kensuke Saito
+// SPDX-License-Identifier: MIT
+
+use super::layout::TextRegion;
+use crate::TextEffectConfig;
+use needle_core::TextRenderer;
+use std::f32::consts::FRAC_1_SQRT_2;
+
+/// Copy of text drawn behind the text to render outline/drop shadow
+pub struct EffectLayer {
+    pub renderer: TextRenderer,
+    pub region: TextRegion,
+    pub spec: LayerSpec,
+}
+
+/// Outline and drop shadow of text.
+/// Effects are drawn as copies of the text rendered with offsets behind the text.
+#[derive(Default)]
+pub struct TextEffect {
+    pub layers: Vec<EffectLayer>,
+    pub font: Option<String>,
+}
+
+/// Offset, color and depth of single effect layer
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LayerSpec {
+    pub offset: [f32; 2],
+    pub color: [u8; 3],
+    /// Depth of layer (Layers must stay behind text at depth 0.0 and in front of background)
+    pub depth: f32,
+}
+
+const EFFECT_DEPTH: f32 = 0.09;
+const EFFECT_DEPTH_STEP: f32 = 0.001;
+const OUTLINE_DIRECTIONS: [[f32; 2]; 8] = [
+    [1.0, 0.0],
+    [-1.0, 0.0],
+    [0.0, 1.0],
+    [0.0, -1.0],
+    [FRAC_1_SQRT_2, FRAC_1_SQRT_2],
+    [FRAC_1_SQRT_2, -FRAC_1_SQRT_2],
+    [-FRAC_1_SQRT_2, FRAC_1_SQRT_2],
+    [-FRAC_1_SQRT_2, -FRAC_1_SQRT_2],
+];
+
+/// Layers required to draw effects, ordered from back to front
+pub fn layer_specs(effect: &TextEffectConfig) -> Vec<LayerSpec> {
+    let mut layers = Vec::new();
+    let shadow = &effect.shadow;
+    let outline = &effect.outline;
+
+    if shadow.enable {
+        layers.push((shadow.offset, shadow.color));
+    }
+    if outline.enable {
+        layers.extend(OUTLINE_DIRECTIONS.iter().map(|direction| {
+            (
+                [direction[0] * outline.width, direction[1] * outline.width],
+                outline.color,
+            )
+        }));
+    }
+
+    layers
+        .into_iter()
+        .enumerate()
+        .map(|(i, (offset, color))| LayerSpec {
+            offset,
+            color,
+            depth: EFFECT_DEPTH - i as f32 * EFFECT_DEPTH_STEP,
+        })
+        .collect()
+}
@@ -0,0 +1,178 @@
+// Copyright 2025 Kensuke Saito
+// SPDX-License-Identifier: MIT
+
+use ab_glyph::{Font, FontVec, PxScale, ScaleFont};
+use font_loader::system_fonts::{self, FontPropertyBuilder};
+use needle_core::Position;
+use winit::dpi::PhysicalSize;
+
+/// Pixel scale `TextRenderer` lays out text with at scale 1.0.
+/// glyph_brush scales fonts so that ascent - descent equals the pixel scale.
+const TEXT_PIXEL_SCALE: f32 = 42.0;
+/// Advance of a single glyph (relative to pixel scale) if the font could not be loaded
+const FALLBACK_ADVANCE: f32 = 0.5;
+
+/// Glyph metrics of the font text is rendered with,
+/// used to compute the bounds of text laid out by `TextRenderer`
+#[derive(Default)]
+pub struct TextMetrics {
+    /// Font family the metrics were loaded for (`None`: default font)
+    family: Option<String>,
+    font: Option<FontVec>,
+    loaded: bool,
+}
+
+impl TextMetrics {
+    /// Load metrics of `family` unless they are already loaded.
+    /// The default font of `TextRenderer` is monospace.
+    pub fn set_font(&mut self, family: Option<&str>) {
+        if self.loaded && self.family.as_deref() == family {
+            return;
+        }
+
+        let property = match family {
+            Some(family) => FontPropertyBuilder::new().family(family).build(),
+            None => FontPropertyBuilder::new().monospace().build(),
+        };
+
+        self.font = system_fonts::get(&property).and_then(|(data, index)| {
+            FontVec::try_from_vec_and_index(data, index as u32)
+                .inspect_err(|e| log::error!("{e}"))
+                .ok()
+        });
+        self.family = family.map(str::to_string);
+        self.loaded = true;
+    }
+
+    /// Size (width, height) of `text` laid out at `scale`
+    pub fn text_size(&self, text: &str, scale: f32) -> (f32, f32) {
+        let pixel_scale = TEXT_PIXEL_SCALE * scale;
+        let lines = text.lines().count().max(1) as f32;
+        let Some(font) = self.font.as_ref() else {
+            let chars = text.lines().map(|line| line.chars().count()).max();
+
+            return (
+                chars.unwrap_or(0) as f32 * FALLBACK_ADVANCE * pixel_scale,
+                lines * pixel_scale,
+            );
+        };
+        let font = font.as_scaled(PxScale::from(pixel_scale));
+        let width = text
+            .lines()
+            .map(|line| {
+                let mut previous = None;
+
+                line.chars()
+                    .map(|c| font.glyph_id(c))
+                    .map(|glyph| {
+                        let kern = previous.map_or(0.0, |previous| font.kern(previous, glyph));
+
+                        previous = Some(glyph);
+                        kern + font.h_advance(glyph)
+                    })
+                    .sum::<f32>()
+            })
+            .fold(0.0, f32::max);
+        let height = lines * font.height() + (lines - 1.0) * font.line_gap();
+
+        (width, height)
+    }
+}
+
+/// Region of the window where text is laid out.
+/// Text is moved by laying it out in a region smaller than the window
+/// and restricting the viewport of the render pass to the same region.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TextRegion {
+    x: f32,
+    y: f32,
+    width: f32,
+    height: f32,
+}
+
+impl TextRegion {
+    /// Region covering the whole window
+    pub fn new(size: &PhysicalSize<u32>) -> Self {
+        Self {
+            x: 0.0,
+            y: 0.0,
+            width: size.width as f32,
+            height: size.height as f32,
+        }
+    }
+
+    /// Region that moves text anchored at `position` by `offset` pixels
+    pub fn with_offset(position: Position, offset: [f32; 2], size: &PhysicalSize<u32>) -> Self {
+        let (anchor_x, anchor_y) = anchor(position);
+        let (x, width) = Self::axis(anchor_x, offset[0], size.width as f32);
+        let (y, height) = Self::axis(anchor_y, offset[1], size.height as f32);
+
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// Size of region to lay out text with
+    pub fn size(&self) -> PhysicalSize<u32> {
+        PhysicalSize::new(self.width as u32, self.height as u32)
+    }
+
+    /// Bounds (left, top, right, bottom) of text anchored at `position`
+    pub fn text_bounds(
+        &self,
+        position: Position,
+        text: &str,
+        scale: f32,
+        margin: f32,
+        metrics: &TextMetrics,
+    ) -> [f32; 4] {
+        let (anchor_x, anchor_y) = anchor(position);
+        let (width, height) = metrics.text_size(text, scale);
+        let (width, height) = (width + 2.0 * margin, height + 2.0 * margin);
+        let left = self.x + anchor_x * (self.width - width);
+        let top = self.y + anchor_y * (self.height - height);
+
+        [left, top, left + width, top + height]
+    }
+
+    /// Restrict rendering to region.
+    /// Text drawn at depth 0.0 is moved to `depth`.
+    pub fn set_viewport(&self, render_pass: &mut wgpu::RenderPass, depth: f32) {
+        render_pass.set_viewport(self.x, self.y, self.width, self.height, depth, 1.0);
+    }
+
+    /// Start and length of region on a single axis.
+    /// Anchor point of text moves by `offset` when the region starts/ends
+    /// at the position computed below. Offsets pushing text outside the window are ignored.
+    fn axis(anchor: f32, offset: f32, length: f32) -> (f32, f32) {
+        let (start, end) = if offset > 0.0 && anchor < 1.0 {
+            ((offset / (1.0 - anchor)).min(length - 1.0), length)
+        } else if offset < 0.0 && anchor > 0.0 {
+            (0.0, (length + offset / anchor).max(1.0))
+        } else {
+            (0.0, length)
+        };
+        let (start, end) = (start.round(), end.round());
+
+        (start, (end - start).max(1.0))
+    }
+}
+
+/// Horizontal and vertical anchor of position.
+/// (0.0: left/top, 0.5: center, 1.0: right/bottom)
+pub fn anchor(position: Position) -> (f32, f32) {
+    match position {
+        Position::Center => (0.5, 0.5),
+        Position::Top => (0.5, 0.0),
+        Position::Bottom => (0.5, 1.0),
+        Position::Left => (0.0, 0.5),
+        Position::Right => (1.0, 0.5),
+        Position::TopLeft => (0.0, 0.0),
+        Position::TopRight => (1.0, 0.0),
+        Position::BottomLeft => (0.0, 1.0),
+        Position::BottomRight => (1.0, 1.0),
+    }
+}
@@ -1,185 +1,388 @@
 // Copyright 2025 Kensuke Saito
 // SPDX-License-Identifier: MIT
 
+mod alert;
+mod background;
 mod base;
+mod clock;
+mod effect;
+mod layout;
+mod playlist;
+mod pomodoro;
+mod shader;
 
-use anyhow::Result;
-use base::NeedleBase;
-use needle_core::{NeedleConfig, NeedleError};
-use std::{
-    cell::RefCell,
-    fs::{self, OpenOptions},
-    io::copy,
-    rc::Rc,
-    time::Instant,
+use crate::{
+    ipc::{Event, Events, Request},
+    process::spawn_command,
+    AlarmAction, AlarmConfig, AppConfig,
 };
+use anyhow::{anyhow, Result};
+use base::NeedleBase;
+use needle_core::NeedleConfig;
+use shader::write_builtin_shaders;
+use std::{cell::RefCell, collections::HashMap, fs, rc::Rc, time::Instant};
 use winit::{
     application::ApplicationHandler,
-    event::{ElementState, KeyEvent, WindowEvent},
+    event::{ElementState, KeyEvent, MouseButton, WindowEvent},
+    event_loop::{ActiveEventLoop, ControlFlow},
     keyboard::{KeyCode, PhysicalKey},
+    window::WindowId,
 };
 
+/// Window with its own clock and settings
+struct ClockWindow<'window> {
+    base: NeedleBase<'window>,
+    config: Rc<RefCell<NeedleConfig>>,
+    app_config: Rc<RefCell<AppConfig>>,
+    /// Index in `windows` of the config file (`None` for the main window)
+    index: Option<usize>,
+}
+
 #[derive(Default)]
 pub struct Needle<'window> {
-    base: Option<NeedleBase<'window>>,
+    windows: HashMap<WindowId, ClockWindow<'window>>,
     config: Option<Rc<RefCell<NeedleConfig>>>,
+    app_config: Option<Rc<RefCell<AppConfig>>>,
+    /// Clients of the control interface receiving events
+    events: Option<Events>,
 }
 
 impl Needle<'_> {
     const APP_NAME: &'static str = env!("CARGO_PKG_NAME");
-    const VERSION: &'static str = env!("CARGO_PKG_VERSION");
     const VERTEX_SHADER_DEFAULT_PATH: &'static str = "shaders/spv/shader.vert.spv";
     const FRAGMENT_SHADER_DEFAULT_PATH: &'static str = "shaders/spv/shader.frag.spv";
-    const RELEASE_URL: &'static str = "https://github.com/bonohub13/needle/releases/download";
 
-    pub fn set_config(&mut self, config: Rc<RefCell<NeedleConfig>>) -> Result<()> {
+    pub fn set_config(
+        &mut self,
+        config: Rc<RefCell<NeedleConfig>>,
+        app_config: Rc<RefCell<AppConfig>>,
+    ) -> Result<()> {
         let shader_path = NeedleConfig::config_path(false, Some("shaders/spv"))?;
         let vert_shader_path =
             NeedleConfig::config_path(false, Some(Self::VERTEX_SHADER_DEFAULT_PATH))?;
         let frag_shader_path =
             NeedleConfig::config_path(false, Some(Self::FRAGMENT_SHADER_DEFAULT_PATH))?;
 
-        if !(vert_shader_path.exists() && frag_shader_path.exists()) {
-            if !shader_path.exists() {
-                fs::create_dir_all(shader_path)?;
-            }
-
-            Self::download_shader()?;
+        if !shader_path.exists() {
+            fs::create_dir_all(shader_path)?;
         }
+        // Compiled on every launch so shaders left by older versions are replaced
+        write_builtin_shaders(&vert_shader_path, &frag_shader_path)?;
 
         self.config = Some(config);
+        self.app_config = Some(app_config);
 
         Ok(())
     }
 
-    /// Download shader from Github
-    fn download_shader() -> Result<()> {
-        let vert_shader = "shader.vert.spv";
-        let frag_shader = "shader.frag.spv";
+    pub fn set_events(&mut self, events: Events) {
+        self.events = Some(events);
+    }
+
+    /// Run action of fired alarm which reaches outside of needle
+    fn alarm_action(alarm: &AlarmConfig, events: Option<&Events>) {
+        match &alarm.action {
+            AlarmAction::Command(command) => {
+                if let Err(e) =
+                    spawn_command(command, &[("NEEDLE_ALARM_LABEL", alarm.label.as_str())])
+                {
+                    log::error!("{e}");
+                }
+            }
+            AlarmAction::Event => {
+                if let Some(events) = events {
+                    events.emit(&Event::Alarm(alarm.label.clone()));
+                }
+            }
+            AlarmAction::None | AlarmAction::Countdown(_) => (),
+        }
+    }
+
+    /// Create main window and additional windows listed in config.
+    /// Additional windows whose profile cannot be loaded or which fail to be created are skipped.
+    fn create_windows(&mut self, event_loop: &ActiveEventLoop) -> Result<()> {
+        let (Some(config), Some(app_config)) = (self.config.clone(), self.app_config.clone())
+        else {
+            return Ok(());
+        };
+        let mut windows = vec![(config, app_config.clone(), None)];
+
+        for (i, window) in app_config.borrow().windows.iter().enumerate() {
+            match app_config.borrow().clock_window_config(window) {
+                Ok((config, app_config)) => windows.push((
+                    Rc::new(RefCell::new(config)),
+                    Rc::new(RefCell::new(app_config)),
+                    Some(i),
+                )),
+                Err(e) => log::error!("windows[{i}]: {e}"),
+            }
+        }
+        for (config, app_config, index) in windows {
+            let title = match index {
+                Some(_) => format!(
+                    "{} - {}",
+                    Self::APP_NAME,
+                    app_config.borrow().profiles.active.as_deref().unwrap_or("")
+                ),
+                None => Self::APP_NAME.to_string(),
+            };
+            let base = match NeedleBase::new(
+                event_loop,
+                config.clone(),
+                &app_config.borrow(),
+                index.is_none(),
+                &title,
+                Self::VERTEX_SHADER_DEFAULT_PATH,
+                Self::FRAGMENT_SHADER_DEFAULT_PATH,
+            ) {
+                Ok(base) => base,
+                Err(e) => match index {
+                    Some(i) => {
+                        log::error!("windows[{i}]: {e}");
+                        continue;
+                    }
+                    None => return Err(e),
+                },
+            };
 
-        Self::write(vert_shader)?;
-        Self::write(frag_shader)?;
+            self.windows.insert(
+                base.window.id(),
+                ClockWindow {
+                    base,
+                    config,
+                    app_config,
+                    index,
+                },
+            );
+        }
 
         Ok(())
     }
 
-    /// Download specified shader
-    fn write(path: &str) -> Result<()> {
-        let write_path =
-            match NeedleConfig::config_path(false, Some(&format!("shaders/spv/{path}"))) {
-                Ok(p) => Ok(p),
-                Err(_) => Err(NeedleError::InvalidPath),
-            }?;
-        let src_url = format!("{}/{}/{}", Self::RELEASE_URL, Self::VERSION, path);
-        let mut file = match OpenOptions::new()
-            .write(true)
-            .create(true)
-            .truncate(true)
-            .open(write_path)
-        {
-            Ok(file) => Ok(file),
-            Err(_) => Err(NeedleError::InvalidPath),
-        }?;
-        let resp = reqwest::blocking::get(&src_url)?;
-        let content = resp.bytes()?;
-
-        log::debug!("URL : {src_url}");
-        copy(&mut content.as_ref(), &mut file)?;
+    /// Close window, saving its geometry.
+    /// Closing the main window quits needle.
+    fn close_window(&mut self, event_loop: &ActiveEventLoop, window_id: WindowId) {
+        if let Some(window) = self.windows.remove(&window_id) {
+            self.save_window_geometry(&window);
+            if window.index.is_none() {
+                event_loop.exit();
+            }
+        }
+    }
 
-        Ok(())
+    fn save_window_geometry(&self, window: &ClockWindow) {
+        let Some(app_config) = self.app_config.as_ref() else {
+            return;
+        };
+
+        match window.index {
+            Some(index) => window
+                .base
+                .save_clock_window_geometry(index, &mut app_config.borrow_mut()),
+            None => window
+                .base
+                .save_window_geometry(&mut app_config.borrow_mut()),
+        }
     }
 }
 
-impl<'a> ApplicationHandler for Needle<'a> {
-    fn resumed(&mut self, event_loop: &winit::event_loop::ActiveEventLoop) {
-        if self.base.is_none() {
-            if let Some(config) = self.config.as_ref() {
-                match NeedleBase::new(
-                    event_loop,
-                    config.clone(),
-                    Self::APP_NAME,
-                    Self::VERTEX_SHADER_DEFAULT_PATH,
-                    Self::FRAGMENT_SHADER_DEFAULT_PATH,
-                ) {
-                    Ok(base) => self.base = Some(base),
-                    Err(e) => panic!("{}", e),
-                }
+impl<'a> ApplicationHandler<Request> for Needle<'a> {
+    fn resumed(&mut self, event_loop: &ActiveEventLoop) {
+        if self.windows.is_empty() {
+            if let Err(e) = self.create_windows(event_loop) {
+                log::error!("{e}");
+                event_loop.exit();
             }
         }
     }
 
+    fn exiting(&mut self, _event_loop: &ActiveEventLoop) {
+        self.windows
+            .values()
+            .for_each(|window| self.save_window_geometry(window));
+    }
+
+    /// Apply command received from another instance to the main window
+    /// and reply with the result
+    fn user_event(&mut self, event_loop: &ActiveEventLoop, request: Request) {
+        let Some((&window_id, window)) = self
+            .windows
+            .iter_mut()
+            .find(|(_, window)| window.index.is_none())
+        else {
+            request.reply(Err(anyhow!("Main window is not open")));
+            return;
+        };
+
+        let result = window.base.control(
+            request.command,
+            &window.config.borrow(),
+            &window.app_config.borrow(),
+        );
+
+        if let Err(e) = &result {
+            log::error!("{e}");
+        }
+        request.reply(result);
+        if window.base.quit {
+            self.close_window(event_loop, window_id);
+        }
+    }
+
+    /// Redraw each window at its own frame limit, sleeping until the earliest next frame
+    fn about_to_wait(&mut self, event_loop: &ActiveEventLoop) {
+        let now = Instant::now();
+
+        self.windows
+            .values()
+            .filter(|window| window.base.next_frame <= now)
+            .for_each(|window| window.base.window.request_redraw());
+
+        // Windows waiting for redraw wake the event loop by themselves
+        let next_frame = self
+            .windows
+            .values()
+            .map(|window| window.base.next_frame)
+            .filter(|next_frame| *next_frame > now)
+            .min();
+
+        event_loop.set_control_flow(match next_frame {
+            Some(next_frame) => ControlFlow::WaitUntil(next_frame),
+            None => ControlFlow::Wait,
+        });
+    }
+
     fn window_event(
         &mut self,
-        event_loop: &winit::event_loop::ActiveEventLoop,
-        window_id: winit::window::WindowId,
-        event: winit::event::WindowEvent,
+        event_loop: &ActiveEventLoop,
+        window_id: WindowId,
+        event: WindowEvent,
     ) {
-        if let (Some(base), Some(config)) = (self.base.as_mut(), self.config.as_ref()) {
-            base.current_frame += 1;
-            match event {
-                WindowEvent::CloseRequested
-                | WindowEvent::KeyboardInput {
-                    event:
-                        KeyEvent {
-                            state: ElementState::Pressed,
-                            physical_key: PhysicalKey::Code(KeyCode::Escape),
-                            ..
-                        },
-                    ..
-                } => {
+        let Some(ClockWindow {
+            base,
+            config,
+            app_config,
+            index,
+        }) = self.windows.get_mut(&window_id)
+        else {
+            return;
+        };
+        let mut close = false;
+
+        base.current_frame += 1;
+        match event {
+            WindowEvent::CloseRequested
+            | WindowEvent::KeyboardInput {
+                event:
+                    KeyEvent {
+                        state: ElementState::Pressed,
+                        physical_key: PhysicalKey::Code(KeyCode::Escape),
+                        ..
+                    },
+                ..
+            } => {
+                base.request_quit(&config.borrow(), &app_config.borrow());
+                close = base.quit;
+            }
+            WindowEvent::KeyboardInput {
+                event:
+                    KeyEvent {
+                        state: ElementState::Pressed,
+                        physical_key: PhysicalKey::Code(KeyCode::Space),
+                        ..
+                    },
+                ..
+            } if !base.wants_keyboard() => {
+                if let Err(e) = base.start_clock() {
+                    log::error!("{e}");
                     event_loop.exit();
                 }
-                WindowEvent::KeyboardInput {
-                    event:
-                        KeyEvent {
-                            state: ElementState::Pressed,
-                            physical_key: PhysicalKey::Code(KeyCode::Space),
-                            ..
-                        },
-                    ..
-                } => {
-                    if let Err(e) = base.start_clock() {
-                        log::error!("{e}");
-                        event_loop.exit();
-                    }
+            }
+            WindowEvent::KeyboardInput {
+                event:
+                    KeyEvent {
+                        state: ElementState::Pressed,
+                        physical_key: PhysicalKey::Code(KeyCode::Insert),
+                        ..
+                    },
+                ..
+            } if !base.wants_keyboard() => {
+                if let Some(imgui_state) = base.imgui_state.as_mut() {
+                    imgui_state.toggle_imgui();
                 }
-                WindowEvent::KeyboardInput {
-                    event:
-                        KeyEvent {
-                            state: ElementState::Pressed,
-                            physical_key: PhysicalKey::Code(KeyCode::Insert),
-                            ..
-                        },
-                    ..
-                } => {
-                    base.imgui_state.toggle_imgui();
+            }
+            WindowEvent::KeyboardInput {
+                event:
+                    KeyEvent {
+                        state: ElementState::Pressed,
+                        physical_key: PhysicalKey::Code(KeyCode::F2),
+                        ..
+                    },
+                ..
+            } if !base.wants_keyboard() => {
+                base.next_profile(&mut config.borrow_mut(), &mut app_config.borrow_mut());
+            }
+            WindowEvent::KeyboardInput {
+                event:
+                    KeyEvent {
+                        state: ElementState::Pressed,
+                        physical_key: PhysicalKey::Code(KeyCode::KeyS),
+                        ..
+                    },
+                ..
+            } if !base.wants_keyboard() => {
+                base.skip_phase(&app_config.borrow());
+            }
+            WindowEvent::CursorMoved { position, .. } => {
+                base.cursor_moved(position, &mut app_config.borrow_mut());
+            }
+            WindowEvent::MouseInput {
+                state,
+                button: MouseButton::Left,
+                ..
+            } => {
+                base.drag_text(state == ElementState::Pressed);
+            }
+            WindowEvent::Resized(physical_size) => {
+                base.resize(&physical_size);
+            }
+            WindowEvent::RedrawRequested => {
+                let frame_time = Instant::now();
+                if let Some(imgui_state) = base.imgui_state.as_mut() {
+                    imgui_state.update(frame_time);
                 }
-                WindowEvent::Resized(physical_size) => {
-                    base.resize(&physical_size);
+                if let Err(err) =
+                    base.render(&mut config.borrow_mut(), &mut app_config.borrow_mut())
+                {
+                    log::error!("{err}");
+
+                    event_loop.exit();
                 }
-                WindowEvent::RedrawRequested => {
-                    /* Check for window has been done in the if statement above */
-                    base.window.request_redraw();
-                    let frame_time = Instant::now();
-                    base.imgui_state.update(frame_time);
-                    if let Err(err) = base.render(&mut config.borrow_mut()) {
-                        log::error!("{err}");
-
-                        event_loop.exit();
+                close = base.quit;
+                // Additional windows show alarms of their profile without running the actions
+                for alarm in base.fired_alarms.drain(..) {
+                    if index.is_none() {
+                        Self::alarm_action(&alarm, self.events.as_ref());
                     }
-                    base.next_frame += base.fps_limit;
+                }
+                // Frames missed while the window was not redrawn (e.g. minimized) are skipped
+                base.next_frame = (base.next_frame
+                    + base.frame_interval(app_config.borrow().timer.precision))
+                .max(frame_time);
 
-                    if (base.fps_update - frame_time) > base.fps_update_limit {
-                        base.fps_update = frame_time;
-                        base.current_frame = 0;
-                    }
-                    std::thread::sleep(base.next_frame - frame_time);
+                if (base.fps_update - frame_time) > base.fps_update_limit {
+                    base.fps_update = frame_time;
+                    base.current_frame = 0;
                 }
-                _ => (),
             }
+            _ => (),
+        }
 
-            base.imgui_state
-                .handle_event(&base.window, window_id, event);
+        if let Some(imgui_state) = base.imgui_state.as_mut() {
+            imgui_state.handle_event(&base.window, window_id, event);
+        }
+        if close {
+            self.close_window(event_loop, window_id);
         }
     }
 }
@@ -0,0 +1,44 @@
+// Copyright 2025 Kensuke Saito
+// SPDX-License-Identifier: MIT
+
+use crate::{PlaylistSegment, PlaylistSegmentMode};
+use std::time::Duration;
+
+/// Progress of timer playlist
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Playlist {
+    /// Index of current segment
+    segment: usize,
+}
+
+impl Playlist {
+    pub fn segment(&self) -> usize {
+        self.segment
+    }
+
+    /// Move to next segment.
+    /// Returns false if the current segment is the last one.
+    pub fn next(&mut self, segments: usize) -> bool {
+        if self.segment + 1 < segments {
+            self.segment += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Whether `segment` has ended with the timer value.
+    /// Count up segments without duration never end by themselves.
+    pub fn is_finished(segment: &PlaylistSegment, timer_value: Duration) -> bool {
+        match (segment.mode, segment.duration) {
+            (PlaylistSegmentMode::Countdown, _) => timer_value.is_zero(),
+            (PlaylistSegmentMode::CountUp, Some(duration)) => timer_value >= duration,
+            (PlaylistSegmentMode::CountUp, None) => false,
+        }
+    }
+
+    /// Segment name shown next to the time (`None` if unnamed)
+    pub fn label(segment: &PlaylistSegment) -> Option<String> {
+        (!segment.name.is_empty()).then(|| segment.name.clone())
+    }
+}
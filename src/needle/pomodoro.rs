@@ -0,0 +1,104 @@
+// Copyright 2025 Kensuke Saito
+// SPDX-License-Identifier: MIT
+
+use crate::{i18n::tr, PomodoroPhase};
+
+/// Progress of Pomodoro mode
+#[derive(Debug, Clone, Copy)]
+pub struct Pomodoro {
+    phase: PomodoroPhase,
+    /// Work round in the current cycle (starting from 1)
+    round: u32,
+}
+
+impl Default for Pomodoro {
+    fn default() -> Self {
+        Self {
+            phase: PomodoroPhase::Work,
+            round: 1,
+        }
+    }
+}
+
+impl Pomodoro {
+    pub fn phase(&self) -> PomodoroPhase {
+        self.phase
+    }
+
+    /// Move to next phase.
+    /// Work is followed by a short break, except the last round of a cycle (long break).
+    pub fn next(&mut self, rounds: u32) {
+        (self.phase, self.round) = match self.phase {
+            PomodoroPhase::Work if self.round >= rounds => (PomodoroPhase::LongBreak, self.round),
+            PomodoroPhase::Work => (PomodoroPhase::ShortBreak, self.round),
+            PomodoroPhase::ShortBreak => (PomodoroPhase::Work, self.round + 1),
+            PomodoroPhase::LongBreak => (PomodoroPhase::Work, 1),
+        };
+    }
+
+    /// Phase name and round shown next to the time (e.g. "Work 2/4")
+    pub fn label(&self, rounds: u32) -> String {
+        format!("{} {}/{rounds}", tr(&self.phase.to_string()), self.round)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cycle_through_phases() {
+        let cases = [
+            (
+                4,
+                [
+                    (PomodoroPhase::ShortBreak, 1),
+                    (PomodoroPhase::Work, 2),
+                    (PomodoroPhase::ShortBreak, 2),
+                    (PomodoroPhase::Work, 3),
+                    (PomodoroPhase::ShortBreak, 3),
+                    (PomodoroPhase::Work, 4),
+                    (PomodoroPhase::LongBreak, 4),
+                    (PomodoroPhase::Work, 1),
+                ],
+            ),
+            (
+                1,
+                [
+                    (PomodoroPhase::LongBreak, 1),
+                    (PomodoroPhase::Work, 1),
+                    (PomodoroPhase::LongBreak, 1),
+                    (PomodoroPhase::Work, 1),
+                    (PomodoroPhase::LongBreak, 1),
+                    (PomodoroPhase::Work, 1),
+                    (PomodoroPhase::LongBreak, 1),
+                    (PomodoroPhase::Work, 1),
+                ],
+            ),
+        ];
+
+        for (rounds, phases) in cases {
+            let mut pomodoro = Pomodoro::default();
+
+            for (phase, round) in phases {
+                pomodoro.next(rounds);
+                assert_eq!(pomodoro.phase, phase, "{rounds} rounds");
+                assert_eq!(pomodoro.round, round, "{rounds} rounds");
+            }
+        }
+    }
+
+    #[test]
+    fn end_cycle_when_rounds_are_reduced() {
+        let mut pomodoro = Pomodoro {
+            phase: PomodoroPhase::Work,
+            round: 3,
+        };
+
+        pomodoro.next(2);
+        assert_eq!(pomodoro.phase, PomodoroPhase::LongBreak);
+        pomodoro.next(2);
+        assert_eq!(pomodoro.phase, PomodoroPhase::Work);
+        assert_eq!(pomodoro.round, 1);
+    }
+}
@@ -0,0 +1,123 @@
+// Copyright 2025 Kensuke Saito
+// SPDX-License-Identifier: MIT
+
+use anyhow::{anyhow, Result};
+use naga::{
+    back::spv,
+    front::wgsl,
+    valid::{Capabilities, ValidationFlags, Validator},
+    ShaderStage,
+};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, Instant, SystemTime},
+};
+
+/// Entry point of shaders expected by `ShaderRenderer`
+const ENTRY_POINT: &str = "main";
+/// Built-in background shaders (solid color, gradients and panel)
+const BUILTIN_VERTEX_SHADER: &str = include_str!("../../shaders/vs_main.wgsl");
+const BUILTIN_FRAGMENT_SHADER: &str = include_str!("../../shaders/fs_main.wgsl");
+
+/// Compile WGSL fragment shader at `source_path` to SPIR-V at `output_path`.
+/// Error messages include the location in the source.
+pub fn compile_wgsl(source_path: &Path, output_path: &Path) -> Result<()> {
+    let source =
+        fs::read_to_string(source_path).map_err(|e| anyhow!("{}: {e}", source_path.display()))?;
+
+    compile_wgsl_source(&source, ShaderStage::Fragment, output_path)
+}
+
+/// Compile built-in background shaders to SPIR-V at given paths.
+/// Shaders are compiled from the sources embedded in the executable,
+/// so they always match the uniform written by this version.
+pub fn write_builtin_shaders(vert_shader_path: &Path, frag_shader_path: &Path) -> Result<()> {
+    compile_wgsl_source(BUILTIN_VERTEX_SHADER, ShaderStage::Vertex, vert_shader_path)?;
+    compile_wgsl_source(
+        BUILTIN_FRAGMENT_SHADER,
+        ShaderStage::Fragment,
+        frag_shader_path,
+    )
+}
+
+fn compile_wgsl_source(source: &str, shader_stage: ShaderStage, output_path: &Path) -> Result<()> {
+    let module = wgsl::parse_str(source).map_err(|e| anyhow!(e.emit_to_string(source)))?;
+    let info = Validator::new(ValidationFlags::all(), Capabilities::empty())
+        .validate(&module)
+        .map_err(|e| anyhow!(e.emit_to_string(source)))?;
+    let words = spv::write_vec(
+        &module,
+        &info,
+        &spv::Options::default(),
+        Some(&spv::PipelineOptions {
+            shader_stage,
+            entry_point: ENTRY_POINT.to_string(),
+        }),
+    )?;
+
+    fs::write(
+        output_path,
+        words
+            .iter()
+            .flat_map(|word| word.to_le_bytes())
+            .collect::<Vec<_>>(),
+    )?;
+
+    Ok(())
+}
+
+/// Watches modification time of shader files to reload them while running
+#[derive(Debug, Default)]
+pub struct ShaderWatcher {
+    files: Vec<(PathBuf, Option<SystemTime>)>,
+    last_check: Option<Instant>,
+}
+
+impl ShaderWatcher {
+    const CHECK_INTERVAL: Duration = Duration::from_millis(500);
+
+    pub fn new(paths: Vec<PathBuf>) -> Self {
+        Self {
+            files: paths
+                .into_iter()
+                .map(|path| {
+                    let modified = Self::modified(&path);
+
+                    (path, modified)
+                })
+                .collect(),
+            last_check: Some(Instant::now()),
+        }
+    }
+
+    /// Check if any of the files has been modified since last check.
+    /// Files are checked at most once every `CHECK_INTERVAL`.
+    pub fn changed(&mut self) -> bool {
+        if self
+            .last_check
+            .is_some_and(|last_check| last_check.elapsed() < Self::CHECK_INTERVAL)
+        {
+            return false;
+        }
+        self.last_check = Some(Instant::now());
+
+        self.files
+            .iter_mut()
+            .fold(false, |changed, (path, modified)| {
+                let current = Self::modified(path);
+
+                if current != *modified {
+                    *modified = current;
+
+                    true
+                } else {
+                    changed
+                }
+            })
+    }
+
+    fn modified(path: &Path) -> Option<SystemTime> {
+        fs::metadata(path).and_then(|meta| meta.modified()).ok()
+    }
+}
@@ -1,9 +1,15 @@
 // Copyright 2025 Kensuke Saito
 // SPDX-License-Identifier: MIT
 
+use crate::{
+    duration::parse_duration,
+    geometry::{parse_geometry, Geometry},
+    i18n::{language, Language},
+};
 use std::{
     env,
     fmt::{self, Display, Formatter},
+    time::Duration,
 };
 
 #[derive(Debug, Default, clap::Parser)]
@@ -28,6 +34,34 @@ pub struct NeedleArgs {
     /// Path for config file
     #[arg(long, short, default_value_t = String::new())]
     pub config: String,
+
+    /// Start countdown timer with specified duration
+    #[arg(long, value_parser = parse_duration)]
+    pub countdown: Option<Duration>,
+
+    /// Validate config file and exit
+    #[arg(long, num_args = 0..=1, default_missing_value = "")]
+    pub check_config: Option<String>,
+
+    /// Upgrade config file to current version and exit
+    #[arg(long, num_args = 0..=1, default_missing_value = "")]
+    pub migrate_config: Option<String>,
+
+    /// Window size and position (WIDTHxHEIGHT+X+Y)
+    #[arg(long, value_parser = parse_geometry)]
+    pub geometry: Option<Geometry>,
+
+    /// Start with named profile
+    #[arg(long, default_value_t = String::new())]
+    pub profile: String,
+
+    /// Send command to running instance and exit
+    #[arg(long, num_args = 1.., value_name = "COMMAND")]
+    pub send: Vec<String>,
+
+    /// Launch even if another instance is running
+    #[arg(long)]
+    pub new_instance: bool,
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -37,6 +71,13 @@ pub enum AppState {
     Version,
     GenerateConfig(String),
     ConfigFilePath(String),
+    Countdown(Duration),
+    Profile(String),
+    CheckConfig(String),
+    MigrateConfig(String),
+    Geometry(Geometry),
+    Send(Vec<String>),
+    NewInstance,
 }
 
 impl AppState {
@@ -44,7 +85,116 @@ impl AppState {
     const NEWLINE: &'static str = "\r\n";
     #[cfg(not(windows))]
     const NEWLINE: &'static str = "\n";
-    const MAX_ARGUMENTS: usize = 5;
+    const MAX_ARGUMENTS: usize = 12;
+    /// Lines of help message in English and Japanese
+    const HELP: [(&'static str, &'static str); 30] = [
+        ("Usage: needle [OPTIONS]", "使用方法: needle [OPTIONS]"),
+        ("", ""),
+        ("Options:", "オプション:"),
+        (
+            "   -h, --help                  Display this message",
+            "   -h, --help                  このメッセージを表示",
+        ),
+        (
+            "   -c, --config [FILENAME]     Specify a path to a custom config file",
+            "   -c, --config [FILENAME]     カスタム設定ファイルのパスを指定",
+        ),
+        (
+            "                               If path of config file is not specified, it will default to default path",
+            "                               設定ファイルのパスが未指定の場合、デフォルトのパスを使用",
+        ),
+        (
+            "   -p, --print                 Output config default values to stdout",
+            "   -p, --print                 デフォルトの設定を標準出力に出力",
+        ),
+        (
+            "       --gen-config [FILENAME] Generates config file",
+            "       --gen-config [FILENAME] 設定ファイルを生成",
+        ),
+        (
+            "                               If path is specified, config file is generated to the specified path",
+            "                               パスが指定された場合、指定されたパスに設定ファイルを生成",
+        ),
+        (
+            "                                   Default path:",
+            "                                   デフォルトのパス:",
+        ),
+        (
+            "                                   - Linux: $HOME/.config/needle/config.toml",
+            "                                   - Linux: $HOME/.config/needle/config.toml",
+        ),
+        (
+            "                                   - Windows: %AppData%\\Roaming\\bonohub13\\needle\\config\\config.toml",
+            "                                   - Windows: %AppData%\\Roaming\\bonohub13\\needle\\config\\config.toml",
+        ),
+        (
+            "       --countdown [DURATION]  Start countdown timer with specified duration",
+            "       --countdown [DURATION]  指定した時間でカウントダウンタイマーを開始",
+        ),
+        (
+            "                               Accepts formats such as 1h30m, 90:00, 1:30:00 and 45s",
+            "                               1h30m, 90:00, 1:30:00, 45s などの形式を使用可能",
+        ),
+        (
+            "       --geometry [GEOMETRY]   Set window size and position (e.g. 640x200+100+100, 640x200, +100+100)",
+            "       --geometry [GEOMETRY]   ウィンドウのサイズと位置を指定 (例: 640x200+100+100, 640x200, +100+100)",
+        ),
+        (
+            "                               Overrides window geometry saved in config file",
+            "                               設定ファイルに保存されたウィンドウのサイズと位置より優先",
+        ),
+        (
+            "       --profile [NAME]        Start with named profile stored in profiles.toml",
+            "       --profile [NAME]        profiles.toml に保存されたプロファイルで起動",
+        ),
+        (
+            "                               Press \"F2\" to switch to next profile while running",
+            "                               実行中は\"F2\"キーで次のプロファイルに切り替え",
+        ),
+        ("       --check-config [FILENAME]", "       --check-config [FILENAME]"),
+        (
+            "                               Validate config file and report problems with line and column",
+            "                               設定ファイルを検証し、問題を行と列の番号と共に表示",
+        ),
+        (
+            "                               If path is not specified, the config file in use is validated",
+            "                               パスが未指定の場合、使用中の設定ファイルを検証",
+        ),
+        ("       --migrate-config [FILENAME]", "       --migrate-config [FILENAME]"),
+        (
+            "                               Upgrade config file to current version and exit",
+            "                               設定ファイルを現在のバージョンに更新して終了",
+        ),
+        (
+            "                               Original file is kept as FILENAME.vN.bak",
+            "                               元のファイルは FILENAME.vN.bak として保存",
+        ),
+        (
+            "       --send [COMMAND]        Send command to running instance and exit",
+            "       --send [COMMAND]        実行中のインスタンスにコマンドを送信して終了",
+        ),
+        (
+            "                               Commands: start, stop, toggle, reset, skip, events, menu, quit,",
+            "                               コマンド: start, stop, toggle, reset, skip, events, menu, quit,",
+        ),
+        (
+            "                                         mode clock, mode countup, mode countdown [DURATION]",
+            "                                         mode clock, mode countup, mode countdown [DURATION]",
+        ),
+        (
+            "       --new-instance          Launch even if another instance is running",
+            "       --new-instance          他のインスタンスが実行中でも起動",
+        ),
+        (
+            "                               The new instance does not receive commands",
+            "                               新しいインスタンスはコマンドを受信しません",
+        ),
+        (
+            "   -v, --version               Print version info and exit",
+            "   -v, --version               バージョン情報を表示して終了",
+        ),
+    ];
+
     pub fn new(args: &NeedleArgs) -> Vec<Self> {
         let mut app_states = Vec::with_capacity(Self::MAX_ARGUMENTS);
 
@@ -69,6 +219,34 @@ impl AppState {
             app_states.push(Self::ConfigFilePath(args.config.clone()));
         }
 
+        if let Some(duration) = args.countdown {
+            app_states.push(Self::Countdown(duration));
+        }
+
+        if let Some(geometry) = args.geometry {
+            app_states.push(Self::Geometry(geometry));
+        }
+
+        if !args.profile.is_empty() {
+            app_states.push(Self::Profile(args.profile.clone()));
+        }
+
+        if let Some(path) = args.check_config.as_ref() {
+            app_states.push(Self::CheckConfig(path.clone()));
+        }
+
+        if let Some(path) = args.migrate_config.as_ref() {
+            app_states.push(Self::MigrateConfig(path.clone()));
+        }
+
+        if !args.send.is_empty() {
+            app_states.push(Self::Send(args.send.clone()));
+        }
+
+        if args.new_instance {
+            app_states.push(Self::NewInstance);
+        }
+
         app_states.push(Self::Run);
 
         app_states
@@ -84,7 +262,16 @@ impl Default for AppState {
 impl Display for AppState {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         let msg = match self {
-            Self::Run | Self::ConfigFilePath(_) | Self::GenerateConfig(_) => String::new(),
+            Self::Run
+            | Self::ConfigFilePath(_)
+            | Self::GenerateConfig(_)
+            | Self::Countdown(_)
+            | Self::Profile(_)
+            | Self::CheckConfig(_)
+            | Self::MigrateConfig(_)
+            | Self::Geometry(_)
+            | Self::Send(_)
+            | Self::NewInstance => String::new(),
             Self::Version => {
                 let app_name = env!("CARGO_PKG_NAME");
                 let app_version = env!("CARGO_PKG_VERSION");
@@ -93,24 +280,11 @@ impl Display for AppState {
                 format!("{app_name} {app_version} ({core_info})")
             }
             Self::Help => {
-                let lines = [
-                    "Usage: needle [OPTIONS]",
-                    "",
-                    "Options:",
-                    "   -h, --help                  Display this message",
-                    "   -c, --config [FILENAME]     Specify a path to a custom config file",
-                    "                               If path of config file is not specified, it will default to default path",
-                    "   -p, --print                 Output config default values to stdout",
-                    "       --gen-config [FILENAME] Generates config file",
-                    "                               If path is specified, config file is generated to the specified path",
-                    "                                   Default path:",
-                    "                                   - Linux: $HOME/.config/needle/config.toml",
-                    "                                   - Windows: %AppData%\\Roaming\\bonohub13\\needle\\config\\config.toml",
-                    "   -v, --version               Print version info and exit",
-                ];
-
-                lines
+                let japanese = language() == Language::Japanese;
+
+                Self::HELP
                     .iter()
+                    .map(|(english, translated)| if japanese { translated } else { english })
                     .map(|s| format!("{}{}", s, Self::NEWLINE))
                     .collect()
             }
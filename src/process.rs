@@ -0,0 +1,35 @@
+// Copyright 2025 Kensuke Saito
+// SPDX-License-Identifier: MIT
+
+use anyhow::Result;
+use std::{process::Command, thread};
+
+/// Run command with the shell in background.
+/// `envs` are passed to the command as environment variables.
+pub fn spawn_command(command: &str, envs: &[(&str, &str)]) -> Result<()> {
+    #[cfg(windows)]
+    let mut shell = {
+        let mut shell = Command::new("cmd");
+
+        shell.args(["/C", command]);
+        shell
+    };
+    #[cfg(not(windows))]
+    let mut shell = {
+        let mut shell = Command::new("sh");
+
+        shell.args(["-c", command]);
+        shell
+    };
+    let mut child = shell.envs(envs.iter().copied()).spawn()?;
+
+    log::info!("Running command \"{command}\"");
+    // Reap the command so it does not linger after exiting
+    thread::spawn(move || match child.wait() {
+        Ok(status) if !status.success() => log::error!("Command exited with {status}"),
+        Ok(_) => (),
+        Err(e) => log::error!("{e}"),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,109 @@
+// Copyright 2025 Kensuke Saito
+// SPDX-License-Identifier: MIT
+
+use crate::migrate::migrate_table;
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Named profiles stored together in `profiles.toml` next to the config file.
+/// Each profile holds the full settings of both `NeedleConfig` and `AppConfig`.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Profiles {
+    profiles: BTreeMap<String, toml::Table>,
+    /// Name of profile currently in use
+    #[serde(skip)]
+    pub active: Option<String>,
+    #[serde(skip)]
+    path: PathBuf,
+}
+
+impl Profiles {
+    const PROFILES_FILE: &'static str = "profiles.toml";
+
+    /// Read profiles stored next to `config_path`.
+    /// Returns empty profiles if the file does not exist.
+    pub fn read(config_path: &Path) -> Result<Self> {
+        let path = config_path.with_file_name(Self::PROFILES_FILE);
+        let mut profiles = if path.exists() {
+            toml::from_str::<Self>(&fs::read_to_string(&path)?)?
+        } else {
+            Self::default()
+        };
+
+        // Profiles saved by older releases are upgraded on read
+        for table in profiles.profiles.values_mut() {
+            migrate_table(table)?;
+        }
+        profiles.path = path;
+
+        Ok(profiles)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        fs::write(&self.path, toml::to_string_pretty(self)?)?;
+
+        Ok(())
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        self.profiles.keys().map(|name| name.as_str()).collect()
+    }
+
+    pub fn get(&self, name: &str) -> Result<&toml::Table> {
+        self.profiles
+            .get(name)
+            .with_context(|| format!("Profile \"{name}\" does not exist"))
+    }
+
+    /// Add/Overwrite profile
+    pub fn insert(&mut self, name: &str, table: toml::Table) -> Result<()> {
+        if name.trim().is_empty() {
+            bail!("Profile name is empty");
+        }
+        self.profiles.insert(name.to_string(), table);
+
+        Ok(())
+    }
+
+    /// Copy profile `from` to new profile `to`
+    pub fn duplicate(&mut self, from: &str, to: &str) -> Result<()> {
+        if self.profiles.contains_key(to) {
+            bail!("Profile \"{to}\" already exists");
+        }
+
+        let table = self.get(from)?.clone();
+
+        self.insert(to, table)
+    }
+
+    pub fn remove(&mut self, name: &str) -> Result<()> {
+        self.get(name)?;
+        self.profiles.remove(name);
+        if self.active.as_deref() == Some(name) {
+            self.active = None;
+        }
+
+        Ok(())
+    }
+
+    /// Name of profile after the active one (wraps around)
+    pub fn next(&self) -> Option<&str> {
+        let names = self.names();
+        let next = match self.active.as_deref() {
+            Some(active) => names
+                .iter()
+                .position(|name| *name == active)
+                .map(|i| (i + 1) % names.len())
+                .unwrap_or(0),
+            None => 0,
+        };
+
+        names.get(next).copied()
+    }
+}